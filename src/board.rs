@@ -1,9 +1,15 @@
 use std::collections::HashSet;
 
-use crate::board_history::BoardHistory;
+use crate::board_history::{BoardHistory, BoardPosition, BoardSnapshot};
 use crate::cell_location::CellLocation;
 use crate::cell_state::{CellSelection, CellState};
-use crate::index::index_to_xy;
+use crate::game_key::GameKey;
+use crate::index::{index_to_xy, xy_to_index};
+use crate::logic::candidate_grid::{mask_digits, CandidateGrid};
+use crate::logic::house::peers;
+use crate::settings::Settings;
+use crate::solver::solve;
+use crate::symmetry::Symmetry;
 use crate::{
     is_legal_index, is_legal_number, BOX_INDEXES, COLUMN_INDEXES, DIGIT_COUNT, ROW_INDEXES,
 };
@@ -32,6 +38,39 @@ pub struct Board {
     pub cell_size: f32,
     pub selected_index: Option<usize>,
     pub selected_number: Option<u8>,
+    // More than one cell picked for an entry to go into, the cursor is one of them.
+    #[serde(skip)]
+    pub selected_cells: Vec<usize>,
+    #[serde(default)]
+    pub solution: [[u8; 9]; 9],
+    #[serde(default)]
+    pub mistakes: u32,
+    // The limit from the settings when the game started, changing the
+    // setting only counts for new games.
+    #[serde(default)]
+    pub mistake_limit: Option<u32>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default = "default_difficulty")]
+    pub difficulty: u8,
+    #[serde(default)]
+    pub symmetry: Symmetry,
+    #[serde(default)]
+    pub minimal: bool,
+    #[serde(default)]
+    pub daily: Option<u32>,
+    // The game's record in the statistics.
+    #[serde(default)]
+    pub game_id: Option<u64>,
+    #[serde(default)]
+    pub hints: u32,
+    // Seconds spent playing, not counting pauses.
+    #[serde(default)]
+    pub elapsed: f64,
+}
+
+fn default_difficulty() -> u8 {
+    2
 }
 
 impl Board {
@@ -49,6 +88,18 @@ impl Board {
             cell_size: 0.0,
             selected_index: None,
             selected_number: None,
+            selected_cells: vec![],
+            solution: [[0; 9]; 9],
+            mistakes: 0,
+            mistake_limit: None,
+            seed: None,
+            difficulty: default_difficulty(),
+            symmetry: Symmetry::None,
+            minimal: false,
+            daily: None,
+            game_id: None,
+            hints: 0,
+            elapsed: 0.0,
         }
     }
 
@@ -68,29 +119,108 @@ impl Board {
     }
 
     pub fn undo(&mut self) {
-        let undo_point = self.history.undo();
-        if undo_point.is_none() {
+        let Some(entry) = self.history.undo() else {
+            return;
+        };
+
+        let position = entry.apply(&mut self.cell_state, true);
+        self.set_position(position);
+    }
+
+    pub fn redo(&mut self) {
+        let Some(entry) = self.history.redo() else {
             return;
+        };
+
+        let position = entry.apply(&mut self.cell_state, false);
+        self.set_position(position);
+    }
+
+    pub fn add_checkpoint(&mut self) -> bool {
+        self.history.add_checkpoint()
+    }
+
+    pub fn go_to_checkpoint(&mut self, checkpoint: usize) {
+        if let Some(checkpoint) = self.history.checkpoints.get(checkpoint) {
+            self.go_to(checkpoint.node);
+        }
+    }
+
+    // Steps back to where the target's branch split off, then along it.
+    pub fn go_to(&mut self, target: Option<usize>) {
+        let path = self.history.path(target);
+        while let Some(current) = self.history.current {
+            if path.contains(&current) {
+                break;
+            }
+            self.undo();
         }
 
-        let undo_point = undo_point.unwrap();
+        self.history.redo_towards(target);
+        while self.history.current != target {
+            self.redo();
+        }
+    }
+
+    // Shows the cells as they were at one node instead of another, leaving
+    // the history where it is.
+    pub fn show_node(&mut self, from: Option<usize>, to: Option<usize>) {
+        let from_path = self.history.path(from);
+        let to_path = self.history.path(to);
+        let mut position = self.snapshot().position;
+        for node in from_path
+            .iter()
+            .rev()
+            .filter(|node| !to_path.contains(node))
+        {
+            position = self.history.nodes[*node]
+                .entry
+                .apply(&mut self.cell_state, true);
+        }
+        for node in to_path.iter().filter(|node| !from_path.contains(node)) {
+            position = self.history.nodes[*node]
+                .entry
+                .apply(&mut self.cell_state, false);
+        }
+        self.set_position(position);
+    }
 
-        self.cell_state = undo_point.cell_state;
-        self.mode = undo_point.mode;
-        self.selected_index = undo_point.selected_index;
-        self.selected_number = undo_point.selected_number;
+    fn set_position(&mut self, position: BoardPosition) {
+        self.mode = position.mode;
+        self.selected_index = position.selected_index;
+        self.selected_number = position.selected_number;
 
         self.highlight();
         self.update_number_count();
     }
 
-    fn add_undo_point(&mut self) {
-        self.history.add_undo_point(
-            &self.cell_state,
-            self.mode,
-            self.selected_index,
-            self.selected_number,
-        );
+    fn snapshot(&self) -> BoardSnapshot {
+        BoardSnapshot {
+            cell_state: self.cell_state,
+            position: BoardPosition {
+                mode: self.mode,
+                selected_index: self.selected_index,
+                selected_number: self.selected_number,
+            },
+        }
+    }
+
+    // Takes the board back to the snapshot without it being redoable.
+    fn discard(&mut self, before: BoardSnapshot) {
+        self.cell_state = before.cell_state;
+        self.set_position(before.position);
+    }
+
+    // Records what changed since the snapshot taken before the action.
+    fn add_undo_point(&mut self, before: BoardSnapshot) {
+        let after = self.snapshot();
+        self.history.add_undo_point(&before, &after, self.elapsed);
+    }
+
+    // Saves from before the history kept deltas have to be converted once loaded.
+    pub fn migrate_history(&mut self) {
+        let current = self.snapshot();
+        self.history.migrate(&current);
     }
 
     fn clear_cell_selection(&mut self) {
@@ -106,19 +236,177 @@ impl Board {
             }
         }
 
+        let before = self.snapshot();
         self.selected_number = Some(number);
-        self.add_undo_point();
+        self.add_undo_point(before);
     }
 
+    // With free entry a full board can still have conflicts in it.
     pub fn is_victory(&self) -> bool {
         for count in self.number_count.iter() {
             if *count != DIGIT_COUNT {
                 return false;
             }
         }
+        self.is_valid()
+    }
+
+    // A game already won isn't lost by the mistakes made on the way.
+    pub fn is_lost(&self) -> bool {
+        self.mistake_limit
+            .is_some_and(|limit| self.mistakes >= limit)
+            && !self.is_victory()
+    }
+
+    pub fn givens(&self) -> [[u8; 9]; 9] {
+        let mut givens = [[0; 9]; 9];
+        for (i, cell) in self.cell_state.iter().enumerate() {
+            if cell.initial {
+                let (x, y) = index_to_xy(i, DIGIT_COUNT as usize);
+                givens[y][x] = cell.number.unwrap_or(0);
+            }
+        }
+        givens
+    }
+
+    // Imported puzzles have no seed, so there's nothing to generate them again from.
+    pub fn game_key(&self) -> Option<GameKey> {
+        self.seed.map(|seed| GameKey {
+            seed,
+            difficulty: self.difficulty,
+            symmetry: self.symmetry,
+            minimal: self.minimal,
+        })
+    }
+
+    // Saves from before the solution was kept get it solved from the givens.
+    pub fn ensure_solution(&mut self) {
+        if self.solution != [[0; 9]; 9] {
+            return;
+        }
+
+        let mut solution = self.givens();
+        if solve(&mut solution) {
+            self.solution = solution;
+        }
+    }
+
+    fn solution_number(&self, index: usize) -> u8 {
+        let (x, y) = index_to_xy(index, DIGIT_COUNT as usize);
+        self.solution[y][x]
+    }
+
+    // Marks every number the player got wrong, returns how many there are.
+    pub fn check(&mut self) -> usize {
+        let mut wrong = 0;
+        for i in 0..81 {
+            let solution = self.solution_number(i);
+            let cell = &mut self.cell_state[i];
+            cell.wrong = !cell.initial && cell.number.is_some_and(|n| n != solution);
+            if cell.wrong {
+                wrong += 1;
+            }
+        }
+        wrong
+    }
+
+    // Fills in the right number for the selected cell.
+    pub fn reveal(&mut self) -> bool {
+        let Some(index) = self.selected_index else {
+            return false;
+        };
+
+        let number = self.solution_number(index);
+        let cell = &self.cell_state[index];
+        if cell.initial || !is_legal_number(number) || cell.is_number(number) {
+            return false;
+        }
+
+        let before = self.snapshot();
+        self.cell_state[index].set_number(number);
+        self.selected_number = Some(number);
+
+        self.highlight();
+        self.clear_pencil(number);
+        self.update_number_count();
+        self.add_undo_point(before);
         true
     }
 
+    // Back to just the givens, for another try at the same puzzle.
+    pub fn restart(&mut self) {
+        for cell in self.cell_state.iter_mut() {
+            if !cell.initial {
+                *cell = CellState::new();
+            }
+        }
+
+        self.history = BoardHistory::new();
+        self.mode = BoardMode::Normal;
+        self.delete_mode = false;
+        self.selected_index = None;
+        self.selected_number = None;
+        self.selected_cells.clear();
+        self.mistakes = 0;
+        self.elapsed = 0.0;
+        self.hints = 0;
+
+        self.update_number_count();
+        self.highlight();
+    }
+
+    // What the placed numbers leave open for every cell.
+    fn candidates(&self) -> CandidateGrid {
+        let mut values = [0; 81];
+        for (value, cell) in values.iter_mut().zip(self.cell_state.iter()) {
+            *value = cell.number.unwrap_or(0);
+        }
+        CandidateGrid::from_values(&values)
+    }
+
+    // Pencils in every number the row, column and box leave open, for all
+    // empty cells at once.
+    pub fn fill_candidates(&mut self) {
+        let before = self.snapshot();
+        self.write_candidates();
+        self.add_undo_point(before);
+    }
+
+    // A new game starts out with them, so there's nothing to undo back to.
+    pub fn fill_starting_candidates(&mut self) {
+        self.write_candidates();
+    }
+
+    fn write_candidates(&mut self) {
+        let grid = self.candidates();
+        for (index, cell) in self.cell_state.iter_mut().enumerate() {
+            if cell.initial || cell.has_number() {
+                continue;
+            }
+
+            cell.clear_pencil();
+            for number in mask_digits(grid.candidates[index]) {
+                cell.set_pencil(number);
+            }
+        }
+    }
+
+    // Puts a removed number back as a candidate wherever nothing else
+    // rules it out, the emptied cell gets all of its candidates.
+    fn restore_candidates(&mut self, index: usize, number: u8) {
+        let grid = self.candidates();
+        for candidate in mask_digits(grid.candidates[index]) {
+            self.cell_state[index].set_pencil(candidate);
+        }
+
+        for peer in peers(index) {
+            let cell = &mut self.cell_state[peer];
+            if !cell.has_number() && grid.has_candidate(peer, number) {
+                cell.set_pencil(number);
+            }
+        }
+    }
+
     pub fn is_number_done(&self, number: u8) -> bool {
         self.number_count[(number - 1) as usize] == DIGIT_COUNT
     }
@@ -161,94 +449,256 @@ impl Board {
         cell.set_number(number.unwrap())
     }
 
-    pub fn click(&mut self, x: f32, y: f32) {
+    pub fn click(&mut self, x: f32, y: f32, settings: &Settings) {
         if (self.portrait && y >= self.board_size + self.game_padding)
             || (!self.portrait && x >= self.board_size + self.game_padding)
         {
             return;
         }
 
-        let mut clicked_index: Option<usize> = None;
+        let Some(clicked_index) = self.cell_at(x, y) else {
+            // no cell was clicked
+            self.selected_index = None;
+            self.selected_cells.clear();
+            return;
+        };
 
-        // perform a click on each cell to see which one
-        // gets selected
-        for i in 0..81 {
-            let loc = &self.cell_location[i];
-            let clicked = loc.click(x, y);
-            if clicked {
-                clicked_index = Some(i);
-                break;
-            }
+        if settings.cell_first {
+            self.select(clicked_index);
+        } else {
+            self.selected_cells.clear();
+            self.enter(&[clicked_index], self.selected_number, self.mode, settings);
         }
+    }
 
-        // no cell was clicked
-        if clicked_index.is_none() {
-            self.selected_index = None;
+    // perform a click on each cell to see which one is under the point
+    pub fn cell_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..81).find(|i| self.cell_location[*i].click(x, y))
+    }
+
+    // Moves the cursor to the cell, its number gets highlighted.
+    pub fn select(&mut self, index: usize) {
+        self.selected_cells.clear();
+        self.selected_index = Some(index);
+        if let Some(number) = self.cell_state[index].number {
+            self.selected_number = Some(number);
+        }
+        self.highlight();
+    }
+
+    // Adds the cell to the selection, or takes it out if it's already in.
+    // The cell the cursor was on is the first one selected.
+    pub fn toggle_selected(&mut self, index: usize) {
+        self.start_selection();
+        if let Some(i) = self.selected_cells.iter().position(|cell| *cell == index) {
+            self.selected_cells.remove(i);
+        } else {
+            self.selected_cells.push(index);
+        }
+        self.selected_index = Some(index);
+    }
+
+    // Adds the cell to the selection, for dragging and extending with the cursor.
+    pub fn extend_selection(&mut self, index: usize) {
+        self.start_selection();
+        if !self.selected_cells.contains(&index) {
+            self.selected_cells.push(index);
+        }
+        self.selected_index = Some(index);
+    }
+
+    fn start_selection(&mut self) {
+        if self.selected_cells.is_empty() {
+            self.selected_cells.extend(self.selected_index);
+        }
+    }
+
+    // The cells an entry goes into, the selection or else the cursor's cell.
+    fn target_cells(&self) -> Vec<usize> {
+        if self.selected_cells.is_empty() {
+            self.selected_index.into_iter().collect()
+        } else {
+            self.selected_cells.clone()
+        }
+    }
+
+    // Places or pencils the number in the selected cells, for cell first input
+    // and the keyboard cursor.
+    pub fn enter_number(&mut self, number: u8, mode: BoardMode, settings: &Settings) {
+        let cells = self.target_cells();
+        self.enter(&cells, Some(number), mode, settings);
+    }
+
+    // Moves the cursor by whole cells, going off one side comes back on the other.
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let index = self.cursor_neighbour(dx, dy);
+        self.select(index);
+    }
+
+    pub fn extend_cursor(&mut self, dx: i32, dy: i32) {
+        let index = self.cursor_neighbour(dx, dy);
+        self.extend_selection(index);
+    }
+
+    fn cursor_neighbour(&self, dx: i32, dy: i32) -> usize {
+        let (x, y) = index_to_xy(self.selected_index.unwrap_or(0), DIGIT_COUNT as usize);
+        let x = (x as i32 + dx).rem_euclid(DIGIT_COUNT as i32) as usize;
+        let y = (y as i32 + dy).rem_euclid(DIGIT_COUNT as i32) as usize;
+        xy_to_index(x, y, DIGIT_COUNT as usize)
+    }
+
+    pub fn delete_selected(&mut self, settings: &Settings) {
+        let cells = self.target_cells();
+        self.delete(&cells, settings);
+    }
+
+    // Both input modes and the selection end up here, so they share the same
+    // checks, and all the cells together are a single undo point.
+    fn enter(&mut self, cells: &[usize], number: Option<u8>, mode: BoardMode, settings: &Settings) {
+        if self.delete_mode {
+            self.delete(cells, settings);
             return;
         }
 
+        let Some(number) = number else {
+            return;
+        };
+
         // you can't change initial numbers
-        let cell = &self.cell_state[clicked_index.unwrap()];
-        if cell.has_initial_number() {
+        let cells: Vec<usize> = cells
+            .iter()
+            .copied()
+            .filter(|index| !self.cell_state[*index].has_initial_number())
+            .collect();
+        if cells.is_empty() {
             return;
         }
 
-        if self.delete_mode {
-            if cell.has_number() && !cell.has_initial_number() {
-                self.add_undo_point();
-                self.selected_index = clicked_index;
-                self.selected_number = self.cell_state[self.selected_index.unwrap()].number;
-                self.cell_state[self.selected_index.unwrap()].clear_number();
-                self.update_number_count();
-                self.highlight();
-            } else if cell.has_pencil() {
-                self.add_undo_point();
-                self.selected_index = clicked_index;
-                self.cell_state[self.selected_index.unwrap()].clear_pencil();
-                self.highlight();
+        let before = self.snapshot();
+        if let [index] = cells[..] {
+            self.selected_index = Some(index);
+        }
+        self.selected_number = Some(number);
+        self.highlight();
+
+        let changed = if mode == BoardMode::Normal {
+            // a number going into several cells is one mistake at most
+            let mistakes = self.mistakes;
+            let mut placed = false;
+            for index in cells {
+                placed |= self.place_number(index, number, settings);
             }
+            self.mistakes = self.mistakes.min(mistakes + 1);
 
-            return;
+            self.highlight();
+            self.clear_pencil(number);
+            self.update_number_count();
+            placed
+        } else {
+            self.toggle_pencils(&cells, number)
+        };
+
+        if changed {
+            self.add_undo_point(before);
+        } else {
+            self.discard(before);
         }
+    }
 
-        if self.mode == BoardMode::Normal {
-            if !self.can_insert(clicked_index, self.selected_number) {
-                return;
-            }
+    // Returns false if the number was already there or got taken back
+    // for clashing with another one.
+    fn place_number(&mut self, index: usize, number: u8, settings: &Settings) -> bool {
+        let cell = self.cell_state[index];
+        if !self.try_insert(Some(index), Some(number)) {
+            return false;
+        }
 
-            self.add_undo_point();
-            self.selected_index = clicked_index;
+        // a number counts as a mistake once, even if it gets undone right away
+        let solution = self.solution_number(index);
+        let valid = !self.clashes(index);
+        if !valid || (solution != 0 && solution != number) {
+            self.mistakes += 1;
+        }
 
-            if !self.try_insert(self.selected_index, self.selected_number) {
-                return;
-            }
+        if !settings.free_entry && !valid {
+            self.cell_state[index] = cell;
+            return false;
+        }
+        true
+    }
 
-            if !self.is_valid() {
-                self.undo();
-                return;
+    // The pencil mark is taken out if all of the cells have it, otherwise it's
+    // added to the cells it doesn't clash with. Returns false if nothing changed.
+    fn toggle_pencils(&mut self, cells: &[usize], number: u8) -> bool {
+        let cells: Vec<usize> = cells
+            .iter()
+            .copied()
+            .filter(|index| !self.cell_state[*index].has_number())
+            .collect();
+        if cells.is_empty() {
+            return false;
+        }
+
+        if cells
+            .iter()
+            .all(|index| self.cell_state[*index].has_this_pencil(number))
+        {
+            for index in cells {
+                self.cell_state[index].remove_pencil(number);
             }
+            return true;
+        }
 
-            self.highlight();
-            self.clear_pencil(self.selected_number.unwrap());
-            self.update_number_count();
-        } else if self.mode == BoardMode::Pencil {
-            if self.selected_number.is_none() || cell.has_number() {
-                return;
+        let mut changed = false;
+        for index in cells {
+            let cell = &mut self.cell_state[index];
+            if !cell.has_this_pencil(number) && cell.selection == CellSelection::None {
+                cell.set_pencil(number);
+                changed = true;
             }
+        }
+        changed
+    }
 
-            let pencil_number = self.selected_number.unwrap();
-            if cell.has_this_pencil(pencil_number) {
-                self.add_undo_point();
-                self.selected_index = clicked_index;
+    // Numbers are cleared first, a cell with only pencil marks loses them.
+    fn delete(&mut self, cells: &[usize], settings: &Settings) {
+        let cells: Vec<usize> = cells
+            .iter()
+            .copied()
+            .filter(|index| {
+                let cell = &self.cell_state[*index];
+                !cell.has_initial_number() && (cell.has_number() || cell.has_pencil())
+            })
+            .collect();
+        if cells.is_empty() {
+            return;
+        }
 
-                self.cell_state[self.selected_index.unwrap()].remove_pencil(pencil_number);
-            } else if cell.selection == CellSelection::None {
-                self.add_undo_point();
-                self.selected_index = clicked_index;
+        let before = self.snapshot();
+        if let [index] = cells[..] {
+            self.selected_index = Some(index);
+            if let Some(number) = self.cell_state[index].number {
+                self.selected_number = Some(number);
+            }
+        }
+
+        for index in cells {
+            let cell = &mut self.cell_state[index];
+            let Some(number) = cell.number else {
+                cell.clear_pencil();
+                continue;
+            };
 
-                self.cell_state[self.selected_index.unwrap()].set_pencil(pencil_number);
+            cell.clear_number();
+            // placing a number already takes it out of the other cells' pencil marks
+            if settings.auto_candidates {
+                self.restore_candidates(index, number);
             }
         }
+
+        self.update_number_count();
+        self.highlight();
+        self.add_undo_point(before);
     }
 
     fn clear_pencil(&mut self, number: u8) {
@@ -263,6 +713,12 @@ impl Board {
         }
     }
 
+    // Whether the cell's number is also in its row, column or box.
+    fn clashes(&self, index: usize) -> bool {
+        let number = self.cell_state[index].number;
+        number.is_some() && peers(index).any(|peer| self.cell_state[peer].number == number)
+    }
+
     fn is_valid(&self) -> bool {
         for range in BOX_INDEXES {
             if !self.is_range_valid(range) {
@@ -285,6 +741,26 @@ impl Board {
         true
     }
 
+    // Cells sharing their number with another cell in a row, column or box.
+    pub fn conflicts(&self) -> [bool; 81] {
+        let mut conflicts = [false; 81];
+
+        for range in BOX_INDEXES.iter().chain(ROW_INDEXES).chain(COLUMN_INDEXES) {
+            for a in range {
+                let number = self.cell_state[*a].number;
+                if number.is_some()
+                    && range
+                        .iter()
+                        .any(|b| a != b && self.cell_state[*b].number == number)
+                {
+                    conflicts[*a] = true;
+                }
+            }
+        }
+
+        conflicts
+    }
+
     fn is_range_valid(&self, range: &[usize; 9]) -> bool {
         let mut values = HashSet::new();
 
@@ -325,6 +801,19 @@ impl Board {
         }
     }
 
+    // Replaces the normal highlighting while a hint is shown.
+    pub fn highlight_hint(&mut self, hint_cells: &[usize], highlighted_cells: &[usize]) {
+        self.clear_cell_selection();
+
+        for index in highlighted_cells {
+            self.cell_state[*index].selection = CellSelection::Highlighted;
+        }
+
+        for index in hint_cells {
+            self.cell_state[*index].selection = CellSelection::Hint;
+        }
+    }
+
     fn highlight_areas(&mut self, area: &[[usize; 9]; 9], selected_index: usize) {
         for index_row in area.iter() {
             if index_row.contains(&selected_index) {
@@ -364,6 +853,670 @@ impl Board {
 
 #[cfg(test)]
 mod tests {
+    use super::{Board, BoardMode};
+    use crate::settings::Settings;
+
+    fn board() -> Board {
+        // the first row is left to the player
+        let mut solution = [[0; 9]; 9];
+        crate::solver::solve(&mut solution);
+
+        let mut board = Board::new();
+        for (i, cell) in board.cell_state.iter_mut().enumerate().skip(9) {
+            cell.set_initial_number(solution[i / 9][i % 9]);
+        }
+        board.update_number_count();
+        board
+    }
+
     #[test]
     fn test_new_board() {}
+
+    #[test]
+    fn test_solution_from_givens() {
+        let mut board = board();
+        board.ensure_solution();
+
+        let givens = board.givens();
+        assert_eq!(givens[0], [0; 9]);
+        assert_eq!(board.solution[1..], givens[1..]);
+        assert!(board.solution[0].iter().all(|n| *n != 0));
+    }
+
+    #[test]
+    fn test_check() {
+        let mut board = board();
+        board.ensure_solution();
+        let right = board.solution[0][0];
+        let wrong = board.solution[0][1];
+
+        board.cell_state[0].set_number(right);
+        board.cell_state[2].set_number(wrong);
+        assert_eq!(board.check(), 1);
+        assert!(!board.cell_state[0].wrong);
+        assert!(board.cell_state[2].wrong);
+        assert!(!board.cell_state[9].wrong);
+
+        board.cell_state[2].clear_number();
+        assert_eq!(board.check(), 0);
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut board = board();
+        assert!(board.conflicts().iter().all(|c| !c));
+
+        // a second copy of the number below it
+        let below = board.cell_state[9].number.unwrap();
+        board.cell_state[0].set_number(below);
+
+        let conflicts = board.conflicts();
+        assert!(conflicts[0]);
+        assert!(conflicts[9]);
+        assert_eq!(conflicts.iter().filter(|c| **c).count(), 2);
+    }
+
+    #[test]
+    fn test_victory_needs_valid_board() {
+        let mut board = board();
+        board.ensure_solution();
+        for i in 0..9 {
+            board.cell_state[i].set_number(board.solution[0][i]);
+        }
+        board.update_number_count();
+        assert!(board.is_victory());
+
+        // same counts, but two numbers swapped in the first row
+        board.cell_state[0].set_number(board.solution[0][1]);
+        board.cell_state[1].set_number(board.solution[0][0]);
+        assert!(!board.is_victory());
+    }
+
+    #[test]
+    fn test_mistakes() {
+        let mut board = board();
+        board.ensure_solution();
+        board.update(900.0, 0.0, true);
+        let settings = Settings::default();
+
+        // with the second row open too, the first row can take wrong numbers that don't clash
+        for cell in board.cell_state[9..18].iter_mut() {
+            *cell = Default::default();
+        }
+
+        // clicks in the middle of the cell in the first row
+        let click = |board: &mut Board, column: usize, number: u8, settings: &Settings| {
+            board.set_selected_number(number);
+            board.click(column as f32 * 100.0 + 50.0, 50.0, settings);
+        };
+
+        let right = board.solution[0][0];
+        click(&mut board, 0, right, &settings);
+        assert!(board.cell_state[0].is_number(right));
+        assert_eq!(board.mistakes, 0);
+
+        // clashes with the third row and gets undone
+        let clash = board.solution[2][1];
+        click(&mut board, 1, clash, &settings);
+        assert!(!board.cell_state[1].has_number());
+        assert_eq!(board.mistakes, 1);
+
+        // belongs in the second row, so it doesn't clash but is still wrong
+        let column = (3..9).find(|c| board.solution[1][*c] != right).unwrap();
+        let wrong = board.solution[1][column];
+        click(&mut board, column, wrong, &settings);
+        assert!(board.cell_state[column].is_number(wrong));
+        assert_eq!(board.mistakes, 2);
+
+        let free_entry = Settings {
+            free_entry: true,
+            ..Default::default()
+        };
+        click(&mut board, 1, clash, &free_entry);
+        assert!(board.cell_state[1].is_number(clash));
+        assert_eq!(board.mistakes, 3);
+
+        // the clash left on the board doesn't make right numbers elsewhere count
+        let right = board.solution[1][2];
+        board.set_selected_number(right);
+        board.click(250.0, 150.0, &free_entry);
+        assert!(board.cell_state[11].is_number(right));
+        assert_eq!(board.mistakes, 3);
+    }
+
+    #[test]
+    fn test_mistake_limit() {
+        let mut board = board();
+        board.mistakes = 3;
+        assert!(!board.is_lost());
+
+        board.mistake_limit = Some(3);
+        assert!(board.is_lost());
+        board.mistakes = 2;
+        assert!(!board.is_lost());
+
+        // kept with the game
+        let saved = serde_json::to_string(&board).unwrap();
+        let loaded: Board = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded.mistake_limit, Some(3));
+
+        // a won game stays won
+        board.mistakes = 3;
+        board.ensure_solution();
+        for index in 0..9 {
+            board.cell_state[index].set_number(board.solution[0][index]);
+        }
+        board.update_number_count();
+        assert!(board.is_victory());
+        assert!(!board.is_lost());
+    }
+
+    #[test]
+    fn test_restart() {
+        let mut board = board();
+        board.cell_state[0].set_number(1);
+        board.cell_state[1].set_pencil(2);
+        board.mistakes = 2;
+        board.elapsed = 30.0;
+        board.mode = BoardMode::Pencil;
+        board.update_number_count();
+
+        board.restart();
+        assert!(!board.cell_state[0].has_number());
+        assert!(!board.cell_state[1].has_pencil());
+        assert!(board.cell_state[9].has_initial_number());
+        assert_eq!(board.mistakes, 0);
+        assert_eq!(board.elapsed, 0.0);
+        assert_eq!(board.mode, BoardMode::Normal);
+        assert_eq!(board.number_count.iter().sum::<u8>(), 72);
+    }
+
+    #[test]
+    fn test_reveal() {
+        let mut board = board();
+        board.ensure_solution();
+        assert!(!board.reveal());
+
+        board.selected_index = Some(9);
+        assert!(!board.reveal());
+
+        board.selected_index = Some(4);
+        board.cell_state[4].set_pencil(1);
+        assert!(board.reveal());
+        assert!(board.cell_state[4].is_number(board.solution[0][4]));
+        assert!(!board.reveal());
+        assert_eq!(board.number_count.iter().sum::<u8>(), 73);
+
+        board.undo();
+        assert!(!board.cell_state[4].has_number());
+        assert!(board.cell_state[4].has_pencil());
+    }
+
+    // The numbers a cell in the first two rows can take once both are opened.
+    fn column_candidates(board: &Board, column: usize) -> Vec<u8> {
+        let mut numbers = vec![board.solution[0][column], board.solution[1][column]];
+        numbers.sort();
+        numbers
+    }
+
+    fn pencils(board: &Board, index: usize) -> Vec<u8> {
+        board.cell_state[index]
+            .pencil
+            .iter()
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_fill_candidates() {
+        let mut board = board();
+        board.ensure_solution();
+        for cell in board.cell_state[9..18].iter_mut() {
+            *cell = Default::default();
+        }
+
+        board.fill_candidates();
+        for index in 0..18 {
+            assert_eq!(pencils(&board, index), column_candidates(&board, index % 9));
+        }
+        assert!(!board.cell_state[18].has_pencil());
+
+        // all in one step
+        board.undo();
+        assert!(board.cell_state.iter().all(|cell| !cell.has_pencil()));
+
+        // a new game starts with them instead
+        let nodes = board.history.nodes.len();
+        board.fill_starting_candidates();
+        assert_eq!(pencils(&board, 0), column_candidates(&board, 0));
+        assert_eq!(board.history.nodes.len(), nodes);
+        board.undo();
+        assert_eq!(pencils(&board, 0), column_candidates(&board, 0));
+    }
+
+    #[test]
+    fn test_auto_candidates() {
+        let mut board = board();
+        board.ensure_solution();
+        board.update(900.0, 0.0, true);
+        for cell in board.cell_state[9..18].iter_mut() {
+            *cell = Default::default();
+        }
+        let settings = Settings {
+            auto_candidates: true,
+            ..Default::default()
+        };
+
+        board.fill_candidates();
+        let number = board.solution[0][0];
+        board.set_selected_number(number);
+        board.click(50.0, 50.0, &settings);
+        assert!(!board.cell_state[9].has_this_pencil(number));
+
+        // removing it puts the candidates back
+        board.toggle_delete_mode();
+        board.click(50.0, 50.0, &settings);
+        for index in 0..18 {
+            assert_eq!(pencils(&board, index), column_candidates(&board, index % 9));
+        }
+    }
+
+    #[test]
+    fn test_cell_first() {
+        let mut board = board();
+        board.ensure_solution();
+        board.update(900.0, 0.0, true);
+        let settings = Settings {
+            cell_first: true,
+            ..Default::default()
+        };
+
+        // clicking only moves the cursor
+        board.click(150.0, 50.0, &settings);
+        assert_eq!(board.selected_index, Some(1));
+        assert!(board.history.current.is_none());
+
+        let right = board.solution[0][1];
+        board.enter_number(right, BoardMode::Normal, &settings);
+        assert!(board.cell_state[1].is_number(right));
+        assert_eq!(board.mistakes, 0);
+
+        // clashes with the number below it and gets taken back
+        board.click(250.0, 50.0, &settings);
+        let clash = board.solution[1][2];
+        board.enter_number(clash, BoardMode::Normal, &settings);
+        assert!(!board.cell_state[2].has_number());
+        assert_eq!(board.mistakes, 1);
+
+        board.enter_number(board.solution[0][2], BoardMode::Pencil, &settings);
+        assert!(board.cell_state[2].has_this_pencil(board.solution[0][2]));
+
+        board.undo();
+        assert!(!board.cell_state[2].has_pencil());
+        board.delete_selected(&settings);
+        assert!(!board.cell_state[2].has_number());
+        board.select(1);
+        board.delete_selected(&settings);
+        assert!(!board.cell_state[1].has_number());
+    }
+
+    #[test]
+    fn test_move_cursor() {
+        let mut board = board();
+        board.move_cursor(0, 0);
+        assert_eq!(board.selected_index, Some(0));
+
+        board.move_cursor(1, 1);
+        assert_eq!(board.selected_index, Some(10));
+
+        // wraps around the edges
+        board.move_cursor(-2, 0);
+        assert_eq!(board.selected_index, Some(17));
+        board.move_cursor(0, -2);
+        assert_eq!(board.selected_index, Some(80));
+
+        // the number under the cursor is highlighted
+        assert_eq!(board.selected_number, board.cell_state[80].number);
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut board = board();
+        board.select(3);
+        board.toggle_selected(5);
+        assert_eq!(board.selected_cells, vec![3, 5]);
+        board.extend_cursor(1, 0);
+        assert_eq!(board.selected_cells, vec![3, 5, 6]);
+        board.toggle_selected(5);
+        assert_eq!(board.selected_cells, vec![3, 6]);
+
+        board.extend_selection(9);
+        assert_eq!(board.target_cells(), vec![3, 6, 9]);
+
+        board.move_cursor(0, 0);
+        assert!(board.selected_cells.is_empty());
+        assert_eq!(board.target_cells(), vec![9]);
+    }
+
+    #[test]
+    fn test_bulk_entry() {
+        let mut board = board();
+        board.ensure_solution();
+        for cell in board.cell_state[9..18].iter_mut() {
+            *cell = Default::default();
+        }
+        let settings = Settings::default();
+
+        // the top two cells of the first column can only take these two
+        board.select(0);
+        board.toggle_selected(9);
+        let (first, second) = (board.solution[0][0], board.solution[1][0]);
+        board.enter_number(first, BoardMode::Pencil, &settings);
+        board.enter_number(second, BoardMode::Pencil, &settings);
+        for index in [0, 9] {
+            assert_eq!(pencils(&board, index), column_candidates(&board, 0));
+        }
+
+        // one undo takes the mark out of both cells again
+        board.undo();
+        for index in [0, 9] {
+            assert_eq!(pencils(&board, index), vec![first]);
+        }
+
+        // a mark all of them have is toggled off
+        board.enter_number(first, BoardMode::Pencil, &settings);
+        assert!(!board.cell_state[0].has_pencil());
+        assert!(!board.cell_state[9].has_pencil());
+
+        // a number only stays where it doesn't clash
+        board.enter_number(first, BoardMode::Normal, &settings);
+        assert!(board.cell_state[0].is_number(first));
+        assert!(!board.cell_state[9].has_number());
+        assert_eq!(board.mistakes, 1);
+
+        board.enter_number(second, BoardMode::Pencil, &settings);
+        assert_eq!(pencils(&board, 9), vec![second]);
+        board.delete_selected(&settings);
+        assert!(!board.cell_state[0].has_number());
+        assert!(!board.cell_state[9].has_pencil());
+        board.undo();
+        assert!(board.cell_state[0].is_number(first));
+        assert!(board.cell_state[9].has_this_pencil(second));
+
+        // both clash with the first cell, but it's still a single mistake
+        board.select(10);
+        board.toggle_selected(11);
+        board.enter_number(first, BoardMode::Normal, &settings);
+        assert!(!board.cell_state[10].has_number());
+        assert!(!board.cell_state[11].has_number());
+        assert_eq!(board.mistakes, 2);
+    }
+
+    #[test]
+    fn test_redo() {
+        let mut board = board();
+        board.ensure_solution();
+
+        board.selected_index = Some(4);
+        assert!(board.reveal());
+        board.selected_index = Some(5);
+        assert!(board.reveal());
+
+        board.undo();
+        board.undo();
+        assert!(!board.cell_state[4].has_number());
+        assert!(!board.cell_state[5].has_number());
+
+        board.redo();
+        assert!(board.cell_state[4].has_number());
+        assert!(!board.cell_state[5].has_number());
+
+        // undo after redo goes back to where it was
+        board.undo();
+        assert!(!board.cell_state[4].has_number());
+        board.redo();
+
+        // a new action drops what's left to redo
+        board.selected_index = Some(6);
+        assert!(board.reveal());
+        board.redo();
+        assert!(!board.cell_state[5].has_number());
+        assert_eq!(board.number_count.iter().sum::<u8>(), 74);
+
+        let saved = serde_json::to_string(&board).unwrap();
+        let mut loaded: Board = serde_json::from_str(&saved).unwrap();
+        loaded.undo();
+        loaded.redo();
+        assert!(loaded.cell_state[6].has_number());
+    }
+
+    #[test]
+    fn test_history_is_small() {
+        let mut board = board();
+        board.ensure_solution();
+        board.selected_index = Some(4);
+        board.reveal();
+
+        // a single cell changed, not a copy of the whole board
+        let saved = serde_json::to_string(&board.history).unwrap();
+        assert!(saved.len() < 200, "{}", saved);
+    }
+
+    #[test]
+    fn test_history_migration() {
+        let mut board = board();
+        let empty = board.cell_state;
+        board.cell_state[0].set_pencil(3);
+        let pencilled = board.cell_state;
+        board.cell_state[0].set_number(5);
+        let mut redone = board.cell_state;
+        redone[1].set_number(6);
+
+        let mut saved = serde_json::to_value(&board).unwrap();
+        saved["history"] = serde_json::json!({
+            "cell_state_history": [{ "state": empty.to_vec() }, { "state": pencilled.to_vec() }],
+            "mode_history": ["Normal", "Pencil"],
+            "selected_index_history": [null, 0],
+            "selected_number_history": [3, 3],
+            "redo_history": [{
+                "cell_state": redone.to_vec(),
+                "mode": "Normal",
+                "selected_index": 1,
+                "selected_number": 6,
+            }],
+        });
+
+        let mut loaded: Board = serde_json::from_value(saved).unwrap();
+        loaded.migrate_history();
+        assert_eq!(loaded.history.path(loaded.history.current).len(), 2);
+        assert_eq!(loaded.history.redo_nodes.len(), 1);
+
+        loaded.redo();
+        assert!(loaded.cell_state[1].is_number(6));
+        assert_eq!(loaded.selected_index, Some(1));
+
+        loaded.undo();
+        loaded.undo();
+        assert!(!loaded.cell_state[0].has_number());
+        assert!(loaded.cell_state[0].has_this_pencil(3));
+        assert_eq!(loaded.mode, BoardMode::Pencil);
+
+        loaded.undo();
+        assert!(!loaded.cell_state[0].has_pencil());
+        assert_eq!(loaded.selected_index, None);
+
+        // saved again it's in the new form
+        let saved = serde_json::to_string(&loaded).unwrap();
+        let reloaded: Board = serde_json::from_str(&saved).unwrap();
+        assert_eq!(reloaded.history.redo_nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        let mut board = board();
+        board.ensure_solution();
+        let reveal = |board: &mut Board, index: usize| {
+            board.selected_index = Some(index);
+            assert!(board.reveal());
+        };
+
+        reveal(&mut board, 0);
+        assert!(board.add_checkpoint());
+        assert!(!board.add_checkpoint());
+        reveal(&mut board, 1);
+        reveal(&mut board, 2);
+
+        // back to the checkpoint, then a guess that leaves the other branch behind
+        board.go_to_checkpoint(0);
+        assert!(board.cell_state[0].has_number());
+        assert!(!board.cell_state[1].has_number());
+        reveal(&mut board, 5);
+        assert_eq!(board.history.checkpoints.len(), 2);
+        assert_eq!(board.history.checkpoints[1].name, "Branch at move 3");
+
+        // the branch left behind can still be gone to
+        board.go_to_checkpoint(1);
+        assert!(board.cell_state[1].has_number());
+        assert!(board.cell_state[2].has_number());
+        assert!(!board.cell_state[5].has_number());
+        assert_eq!(board.number_count.iter().sum::<u8>(), 75);
+
+        // and the guess is the branch left behind now
+        assert_eq!(board.history.checkpoints.len(), 3);
+        board.go_to_checkpoint(2);
+        assert!(board.cell_state[5].has_number());
+        assert!(!board.cell_state[1].has_number());
+
+        let saved = serde_json::to_string(&board).unwrap();
+        let mut loaded: Board = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded.history.checkpoints, board.history.checkpoints);
+        loaded.go_to_checkpoint(0);
+        assert!(!loaded.cell_state[5].has_number());
+        loaded.redo();
+        assert!(loaded.cell_state[5].has_number());
+    }
+
+    #[test]
+    fn test_branch_limit() {
+        let mut board = board();
+        board.ensure_solution();
+        let reveal = |board: &mut Board, index: usize| {
+            board.selected_index = Some(index);
+            assert!(board.reveal());
+        };
+
+        reveal(&mut board, 0);
+        assert!(board.add_checkpoint());
+
+        // every guess taken back and replaced leaves a branch behind
+        for index in 1..8 {
+            reveal(&mut board, index);
+            board.undo();
+        }
+        reveal(&mut board, 8);
+
+        let checkpoints = &board.history.checkpoints;
+        assert_eq!(checkpoints.len(), 6);
+        assert_eq!(checkpoints[0].name, "Checkpoint 1");
+        assert_eq!(checkpoints.iter().filter(|c| c.branch).count(), 5);
+
+        // the oldest branches went first
+        board.go_to_checkpoint(1);
+        assert!(board.cell_state[3].has_number());
+    }
+
+    #[test]
+    fn test_moves() {
+        let mut board = board();
+        board.ensure_solution();
+        let reveal = |board: &mut Board, index: usize| {
+            board.elapsed += 1.0;
+            board.selected_index = Some(index);
+            assert!(board.reveal());
+        };
+
+        reveal(&mut board, 0);
+        reveal(&mut board, 1);
+        board.undo();
+        reveal(&mut board, 2);
+
+        // the undone move stays in, in the order it was made
+        let moves = board.history.moves();
+        assert_eq!(moves, vec![0, 1, 2]);
+        assert_eq!(board.history.path(board.history.current), vec![0, 2]);
+        let times: Vec<f64> = moves
+            .iter()
+            .map(|node| board.history.nodes[*node].time)
+            .collect();
+        assert_eq!(times, vec![1.0, 2.0, 3.0]);
+
+        // ending on the board the game is at
+        board.go_to(Some(1));
+        assert_eq!(board.history.moves(), vec![0, 1, 2, 1]);
+    }
+
+    #[test]
+    fn test_replay_keeps_history() {
+        let mut board = board();
+        board.ensure_solution();
+        let reveal = |board: &mut Board, index: usize| {
+            board.selected_index = Some(index);
+            assert!(board.reveal());
+        };
+
+        reveal(&mut board, 0);
+        assert!(board.add_checkpoint());
+        reveal(&mut board, 1);
+        board.undo();
+        reveal(&mut board, 2);
+        reveal(&mut board, 3);
+        board.undo();
+
+        let checkpoints = board.history.checkpoints.clone();
+        let redo_nodes = board.history.redo_nodes.clone();
+        let current = board.history.current;
+        let cells: Vec<Option<u8>> = board.cell_state.iter().map(|cell| cell.number).collect();
+
+        // each move shows its own branch, the left behind one included
+        let mut shown = current;
+        for node in board.history.moves() {
+            board.show_node(shown, Some(node));
+            shown = Some(node);
+            if node == 1 {
+                assert!(board.cell_state[1].has_number());
+                assert!(!board.cell_state[2].has_number());
+            }
+        }
+        board.show_node(shown, current);
+
+        assert_eq!(board.history.checkpoints, checkpoints);
+        assert_eq!(board.history.redo_nodes, redo_nodes);
+        assert_eq!(board.history.current, current);
+        let after: Vec<Option<u8>> = board.cell_state.iter().map(|cell| cell.number).collect();
+        assert_eq!(after, cells);
+    }
+
+    #[test]
+    fn test_delta_history_migration() {
+        let mut board = board();
+        board.ensure_solution();
+        board.selected_index = Some(4);
+        board.reveal();
+        board.selected_index = Some(5);
+        board.reveal();
+        board.undo();
+
+        let mut saved = serde_json::to_value(&board).unwrap();
+        let nodes = saved["history"]["nodes"].as_array().unwrap().clone();
+        let entries: Vec<_> = nodes.iter().map(|node| node["e"].clone()).collect();
+        saved["history"] = serde_json::json!({ "undo": [entries[0]], "redo": [entries[1]] });
+
+        let mut loaded: Board = serde_json::from_value(saved).unwrap();
+        loaded.redo();
+        assert!(loaded.cell_state[5].has_number());
+        loaded.undo();
+        loaded.undo();
+        assert!(!loaded.cell_state[4].has_number());
+    }
 }
@@ -2,79 +2,459 @@ use crate::{board::BoardMode, cell_state::CellState};
 use serde_big_array::BigArray;
 use serde_derive::{Deserialize, Serialize};
 
-pub struct BoardUndoPoint {
-    pub cell_state: [CellState; 81],
+// Only the latest branches left behind are kept as checkpoints.
+const MAX_BRANCHES: usize = 5;
+
+// The history is saved with the board after every action, so the saved
+// names are kept to a letter.
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BoardPosition {
+    #[serde(rename = "m")]
     pub mode: BoardMode,
+    #[serde(rename = "i")]
     pub selected_index: Option<usize>,
+    #[serde(rename = "n")]
     pub selected_number: Option<u8>,
 }
 
+// The whole board at one point, to work out what an action changed.
+#[derive(Clone, Copy)]
+pub struct BoardSnapshot {
+    pub cell_state: [CellState; 81],
+    pub position: BoardPosition,
+}
+
+// A cell's number in the low four bits, with a bit for each pencil mark above it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(transparent)]
+pub struct CellValue(u16);
+
+impl CellValue {
+    fn from_cell(cell: &CellState) -> Self {
+        let mut value = cell.number.unwrap_or(0) as u16;
+        for (i, pencil) in cell.pencil.iter().enumerate() {
+            if pencil.is_some() {
+                value |= 1 << (i + 4);
+            }
+        }
+
+        CellValue(value)
+    }
+
+    fn apply(self, cell: &mut CellState) {
+        let number = match self.0 & 0xf {
+            0 => None,
+            number => Some(number as u8),
+        };
+        if cell.number != number {
+            cell.wrong = false;
+        }
+        cell.number = number;
+
+        for (i, pencil) in cell.pencil.iter_mut().enumerate() {
+            *pencil = if self.0 & (1 << (i + 4)) != 0 {
+                Some(i as u8 + 1)
+            } else {
+                None
+            };
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CellChange {
+    #[serde(rename = "i")]
+    pub index: u8,
+    #[serde(rename = "b")]
+    pub before: CellValue,
+    #[serde(rename = "a")]
+    pub after: CellValue,
+}
+
+// One action, as the cells it changed and where the board was before and after.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct CellStates {
-    #[serde(with = "BigArray")]
-    state: [CellState; 81],
+pub struct HistoryEntry {
+    #[serde(rename = "c")]
+    pub changes: Vec<CellChange>,
+    #[serde(rename = "b")]
+    pub before: BoardPosition,
+    #[serde(rename = "a")]
+    pub after: BoardPosition,
 }
 
+impl HistoryEntry {
+    fn new(before: &BoardSnapshot, after: &BoardSnapshot) -> Self {
+        let changes = before
+            .cell_state
+            .iter()
+            .zip(after.cell_state.iter())
+            .enumerate()
+            .filter_map(|(index, (before, after))| {
+                let before = CellValue::from_cell(before);
+                let after = CellValue::from_cell(after);
+                (before != after).then_some(CellChange {
+                    index: index as u8,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+
+        HistoryEntry {
+            changes,
+            before: before.position,
+            after: after.position,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.before == self.after
+    }
+
+    // Puts the cells back the way they were before the action when undone,
+    // otherwise the way the action left them.
+    pub fn apply(&self, cell_state: &mut [CellState; 81], undone: bool) -> BoardPosition {
+        for change in self.changes.iter() {
+            let value = if undone { change.before } else { change.after };
+            value.apply(&mut cell_state[change.index as usize]);
+        }
+
+        if undone {
+            self.before
+        } else {
+            self.after
+        }
+    }
+}
+
+// An action in the history tree, going from its parent's board to this one.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HistoryNode {
+    #[serde(rename = "p")]
+    pub parent: Option<usize>,
+    #[serde(rename = "e")]
+    pub entry: HistoryEntry,
+    // Seconds into the game the action was made.
+    #[serde(rename = "t", default)]
+    pub time: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub name: String,
+    // None for the board before any action.
+    pub node: Option<usize>,
+    // Added for a branch left behind rather than by the player.
+    #[serde(default)]
+    pub branch: bool,
+}
+
+// Undoing keeps the actions in the tree, so a branch left behind by a new
+// action can still be gone back to.
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(from = "SavedHistory")]
 pub struct BoardHistory {
-    pub cell_state_history: Vec<CellStates>,
-    pub mode_history: Vec<BoardMode>,
-    pub selected_index_history: Vec<Option<usize>>,
-    pub selected_number_history: Vec<Option<u8>>,
+    pub nodes: Vec<HistoryNode>,
+    // The node the board is at, None before the first action.
+    pub current: Option<usize>,
+    // Nodes taken back by undo, the last one is redone first.
+    #[serde(rename = "redo")]
+    pub redo_nodes: Vec<usize>,
+    pub checkpoints: Vec<Checkpoint>,
+    // A save from before the history kept deltas, until the board converts it.
+    #[serde(skip)]
+    snapshots: Option<SnapshotHistory>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedHistory {
+    Snapshots(SnapshotHistory),
+    Tree {
+        nodes: Vec<HistoryNode>,
+        current: Option<usize>,
+        #[serde(default)]
+        redo: Vec<usize>,
+        #[serde(default)]
+        checkpoints: Vec<Checkpoint>,
+    },
+    Deltas {
+        #[serde(default)]
+        undo: Vec<HistoryEntry>,
+        #[serde(default)]
+        redo: Vec<HistoryEntry>,
+    },
+}
+
+impl From<SavedHistory> for BoardHistory {
+    fn from(saved: SavedHistory) -> Self {
+        match saved {
+            SavedHistory::Snapshots(snapshots) => BoardHistory {
+                snapshots: Some(snapshots),
+                ..BoardHistory::new()
+            },
+            SavedHistory::Tree {
+                nodes,
+                current,
+                redo,
+                checkpoints,
+            } => BoardHistory {
+                nodes,
+                current,
+                redo_nodes: redo,
+                checkpoints,
+                snapshots: None,
+            },
+            SavedHistory::Deltas { undo, redo } => {
+                let mut history = BoardHistory::new();
+                history.set_line(undo, redo);
+                history
+            }
+        }
+    }
+}
+
+// The old history, a full copy of the cells from before every action.
+#[derive(Deserialize, Debug)]
+struct SnapshotHistory {
+    cell_state_history: Vec<CellStates>,
+    mode_history: Vec<BoardMode>,
+    selected_index_history: Vec<Option<usize>>,
+    selected_number_history: Vec<Option<u8>>,
+    #[serde(default)]
+    redo_history: Vec<SnapshotPoint>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CellStates {
+    #[serde(with = "BigArray")]
+    state: [CellState; 81],
+}
+
+#[derive(Deserialize, Debug)]
+struct SnapshotPoint {
+    #[serde(with = "BigArray")]
+    cell_state: [CellState; 81],
+    mode: BoardMode,
+    selected_index: Option<usize>,
+    selected_number: Option<u8>,
+}
+
+impl SnapshotPoint {
+    fn to_snapshot(&self) -> BoardSnapshot {
+        BoardSnapshot {
+            cell_state: self.cell_state,
+            position: BoardPosition {
+                mode: self.mode,
+                selected_index: self.selected_index,
+                selected_number: self.selected_number,
+            },
+        }
+    }
+}
+
+// Each step from one board to the next, leaving out the ones that changed nothing.
+fn entries_between(snapshots: &[BoardSnapshot]) -> Vec<HistoryEntry> {
+    snapshots
+        .windows(2)
+        .map(|pair| HistoryEntry::new(&pair[0], &pair[1]))
+        .filter(|entry| !entry.is_empty())
+        .collect()
 }
 
 impl BoardHistory {
     pub fn new() -> Self {
         BoardHistory {
-            cell_state_history: vec![],
-            mode_history: vec![],
-            selected_index_history: vec![],
-            selected_number_history: vec![],
+            nodes: vec![],
+            current: None,
+            redo_nodes: vec![],
+            checkpoints: vec![],
+            snapshots: None,
         }
     }
 
-    pub fn undo(&mut self) -> Option<BoardUndoPoint> {
-        if self.cell_state_history.is_empty() {
-            return None;
+    // The undone action, to be applied backwards.
+    pub fn undo(&mut self) -> Option<&HistoryEntry> {
+        let current = self.current?;
+        self.redo_nodes.push(current);
+        self.current = self.nodes[current].parent;
+        Some(&self.nodes[current].entry)
+    }
+
+    pub fn redo(&mut self) -> Option<&HistoryEntry> {
+        let node = self.redo_nodes.pop()?;
+        self.current = Some(node);
+        Some(&self.nodes[node].entry)
+    }
+
+    pub fn add_undo_point(&mut self, before: &BoardSnapshot, after: &BoardSnapshot, time: f64) {
+        let entry = HistoryEntry::new(before, after);
+        if entry.is_empty() {
+            return;
         }
 
-        let cell_state = self.cell_state_history.pop().unwrap();
-        let mode = self.mode_history.pop().unwrap();
+        // a new action starts a new branch, so there's nothing left to redo
+        self.keep_branch();
+        self.redo_nodes.clear();
+        self.nodes.push(HistoryNode {
+            parent: self.current,
+            entry,
+            time,
+        });
+        self.current = Some(self.nodes.len() - 1);
+    }
 
-        self.selected_index_history.pop();
-        let selected_index = if self.selected_index_history.is_empty() {
-            None
-        } else {
-            *self.selected_index_history.last().unwrap()
+    // The nodes from the first action up to and including the node.
+    pub fn path(&self, node: Option<usize>) -> Vec<usize> {
+        let mut path = vec![];
+        let mut node = node;
+        while let Some(index) = node {
+            path.push(index);
+            node = self.nodes[index].parent;
+        }
+
+        path.reverse();
+        path
+    }
+
+    // Every action in the order it was made, undone moves and branches left
+    // behind included, ending on the board the history is at.
+    pub fn moves(&self) -> Vec<usize> {
+        let mut moves: Vec<usize> = (0..self.nodes.len()).collect();
+        if let Some(current) = self.current {
+            if moves.last() != Some(&current) {
+                moves.push(current);
+            }
+        }
+        moves
+    }
+
+    pub fn add_checkpoint(&mut self) -> bool {
+        if self
+            .checkpoints
+            .iter()
+            .any(|checkpoint| checkpoint.node == self.current)
+        {
+            return false;
+        }
+
+        let added = self.checkpoints.iter().filter(|c| !c.branch).count();
+        self.checkpoints.push(Checkpoint {
+            name: format!("Checkpoint {}", added + 1),
+            node: self.current,
+            branch: false,
+        });
+        true
+    }
+
+    // Sets up redo to lead from the current node, which has to be on the
+    // target's path, down to the target.
+    pub fn redo_towards(&mut self, target: Option<usize>) {
+        let Some(target) = target else {
+            return;
         };
+        if self.current == Some(target) || self.redo_nodes.contains(&target) {
+            return;
+        }
 
-        self.selected_number_history.pop();
-        let selected_number = if self.selected_number_history.is_empty() {
-            None
-        } else {
-            *self.selected_number_history.last().unwrap()
+        self.keep_branch();
+        let depth = self.path(self.current).len();
+        self.redo_nodes = self.path(Some(target))[depth..]
+            .iter()
+            .rev()
+            .copied()
+            .collect();
+    }
+
+    // Marks the end of the branch that redo would follow before it's dropped.
+    fn keep_branch(&mut self) {
+        let Some(&end) = self.redo_nodes.first() else {
+            return;
         };
+        if self
+            .checkpoints
+            .iter()
+            .any(|checkpoint| checkpoint.node == Some(end))
+        {
+            return;
+        }
+
+        let branches: Vec<usize> = (0..self.checkpoints.len())
+            .filter(|i| self.checkpoints[*i].branch)
+            .collect();
+        if branches.len() >= MAX_BRANCHES {
+            self.checkpoints.remove(branches[0]);
+        }
 
-        Some(BoardUndoPoint {
-            cell_state: cell_state.state,
-            mode,
-            selected_index,
-            selected_number,
-        })
-    }
-
-    pub fn add_undo_point(
-        &mut self,
-        cell_states: &[CellState; 81],
-        mode: BoardMode,
-        selected_index: Option<usize>,
-        selected_number: Option<u8>,
-    ) {
-        self.cell_state_history.push(CellStates {
-            state: *cell_states,
+        self.checkpoints.push(Checkpoint {
+            name: format!("Branch at move {}", self.path(Some(end)).len()),
+            node: Some(end),
+            branch: true,
         });
-        self.mode_history.push(mode);
-        self.selected_index_history.push(selected_index);
-        self.selected_number_history.push(selected_number);
+    }
+
+    // Replaces the history with a single line of actions.
+    fn set_line(&mut self, undo: Vec<HistoryEntry>, redo: Vec<HistoryEntry>) {
+        self.nodes.clear();
+        for entry in undo {
+            self.nodes.push(HistoryNode {
+                parent: self.nodes.len().checked_sub(1),
+                entry,
+                time: 0.0,
+            });
+        }
+        self.current = self.nodes.len().checked_sub(1);
+
+        // redo pops the next action from the end
+        self.redo_nodes.clear();
+        for entry in redo.into_iter().rev() {
+            self.redo_nodes.push(self.nodes.len());
+            self.nodes.push(HistoryNode {
+                parent: self.nodes.len().checked_sub(1),
+                entry,
+                time: 0.0,
+            });
+        }
+        self.redo_nodes.reverse();
+    }
+
+    // Turns a loaded snapshot history into deltas, ending at the current board.
+    pub fn migrate(&mut self, current: &BoardSnapshot) {
+        let Some(old) = self.snapshots.take() else {
+            return;
+        };
+
+        let mut snapshots: Vec<BoardSnapshot> = old
+            .cell_state_history
+            .iter()
+            .zip(old.mode_history.iter())
+            .zip(old.selected_index_history.iter())
+            .zip(old.selected_number_history.iter())
+            .map(
+                |(((cells, mode), selected_index), selected_number)| BoardSnapshot {
+                    cell_state: cells.state,
+                    position: BoardPosition {
+                        mode: *mode,
+                        selected_index: *selected_index,
+                        selected_number: *selected_number,
+                    },
+                },
+            )
+            .collect();
+        snapshots.push(*current);
+
+        // redone from the current board onwards, in the opposite order they are popped
+        let mut redo_snapshots = vec![*current];
+        redo_snapshots.extend(
+            old.redo_history
+                .iter()
+                .rev()
+                .map(|point| point.to_snapshot()),
+        );
+        let mut redo = entries_between(&redo_snapshots);
+        redo.reverse();
+
+        self.set_line(entries_between(&snapshots), redo);
     }
 }
@@ -7,6 +7,7 @@ pub enum CellSelection {
     None,
     Emphasized,
     Highlighted,
+    Hint,
 }
 
 #[derive(Clone, Copy, Serialize, Deserialize, Debug)]
@@ -15,6 +16,9 @@ pub struct CellState {
     pub pencil: [Option<u8>; 9],
     pub selection: CellSelection,
     pub initial: bool,
+    // Set when checking the board found the number to be wrong.
+    #[serde(default)]
+    pub wrong: bool,
 }
 
 impl Default for CellState {
@@ -30,6 +34,7 @@ impl CellState {
             pencil: [None, None, None, None, None, None, None, None, None],
             selection: CellSelection::None,
             initial: false,
+            wrong: false,
         }
     }
 
@@ -95,6 +100,7 @@ impl CellState {
 
         self.clear_pencil();
         self.number = Some(number);
+        self.wrong = false;
         true
     }
 
@@ -104,6 +110,7 @@ impl CellState {
         }
 
         self.number = None;
+        self.wrong = false;
     }
 }
 
@@ -180,6 +187,25 @@ mod tests {
         assert!(!cell.is_number(2));
     }
 
+    #[test]
+    fn wrong_test() {
+        let mut cell = CellState::new();
+        cell.set_number(1);
+        cell.wrong = true;
+
+        cell.set_number(2);
+        assert!(!cell.wrong);
+
+        cell.wrong = true;
+        cell.clear_number();
+        assert!(!cell.wrong);
+
+        cell.set_number(3);
+        cell.wrong = true;
+        cell.set_pencil(4);
+        assert!(!cell.wrong);
+    }
+
     #[test]
     fn pencil_test() {
         let mut cell = CellState::new();
@@ -0,0 +1,151 @@
+use crate::text_button::TextButton;
+
+// The checkpoints are shown a page at a time.
+const ROWS_PER_PAGE: usize = 6;
+
+pub struct CheckpointsModal {
+    pub show: bool,
+    pub x: f32,
+    pub y: f32,
+    pub game_square: f32,
+    pub width: f32,
+    pub height: f32,
+    pub title_height: f32,
+    pub checkpoints: usize,
+    pub page: usize,
+    pub rows: Vec<TextButton>,
+    pub add: TextButton,
+    pub previous: TextButton,
+    pub next: TextButton,
+}
+
+impl Default for CheckpointsModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CheckpointsModal {
+    pub fn new() -> Self {
+        CheckpointsModal {
+            show: false,
+            x: 0.0,
+            y: 0.0,
+            game_square: 0.0,
+            width: 0.0,
+            height: 0.0,
+            title_height: 0.0,
+            checkpoints: 0,
+            page: 0,
+            rows: vec![],
+            add: Default::default(),
+            previous: Default::default(),
+            next: Default::default(),
+        }
+    }
+
+    // Opens on the last page, where the newest checkpoints are.
+    pub fn show(&mut self, checkpoints: usize) {
+        self.show = true;
+        self.checkpoints = checkpoints;
+        self.page = self.page_count() - 1;
+
+        self.update_checkpoints();
+    }
+
+    pub fn hide(&mut self) {
+        self.show = false;
+    }
+
+    pub fn update(&mut self, game_square: f32) {
+        self.game_square = game_square;
+
+        if self.show {
+            self.update_checkpoints();
+        }
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.checkpoints.div_ceil(ROWS_PER_PAGE).max(1)
+    }
+
+    // The checkpoint in the first row.
+    pub fn first(&self) -> usize {
+        self.page * ROWS_PER_PAGE
+    }
+
+    pub fn next_page(&mut self) {
+        self.page = (self.page + 1) % self.page_count();
+        self.update_checkpoints();
+    }
+
+    pub fn previous_page(&mut self) {
+        self.page = (self.page + self.page_count() - 1) % self.page_count();
+        self.update_checkpoints();
+    }
+
+    pub fn update_checkpoints(&mut self) {
+        let cell_width = self.game_square / 9.0;
+        let row_height = cell_width * 0.75;
+        let row_count = (self.checkpoints - self.first()).min(ROWS_PER_PAGE);
+        let paged = self.page_count() > 1;
+
+        // an empty list still gets a row for saying so, and with more than
+        // one page every page is as tall as a full one so the buttons stay put
+        let list_rows = if paged {
+            ROWS_PER_PAGE
+        } else {
+            row_count.max(1)
+        };
+        self.width = cell_width * 5.0;
+        self.x = self.game_square / 2.0 - (self.width / 2.0);
+        self.title_height = cell_width * 0.75;
+        self.height = self.title_height + (list_rows + 1 + paged as usize) as f32 * row_height;
+        self.y = self.game_square / 2.0 - (self.height / 2.0);
+
+        let mut button = TextButton::new();
+        self.rows.clear();
+        for i in 0..row_count {
+            button.update(
+                self.x + (cell_width * 0.25),
+                self.y + self.title_height + (i as f32 * row_height),
+                self.width - (cell_width * 0.5),
+                cell_width * 0.5,
+            );
+            self.rows.push(button);
+        }
+
+        let add_y = self.y + self.title_height + (list_rows as f32 * row_height);
+        self.add.update(
+            self.x + (cell_width * 0.25),
+            add_y,
+            self.width - (cell_width * 0.5),
+            cell_width * 0.5,
+        );
+
+        // back and next share the row under the add button, when there are pages
+        let gap = cell_width * 0.15;
+        let button_width = (self.width - (cell_width * 0.5) - gap) / 2.0;
+        let buttons = [&mut self.previous, &mut self.next];
+        for (i, button) in buttons.into_iter().enumerate() {
+            button.update(
+                self.x + (cell_width * 0.25) + (i as f32 * (button_width + gap)),
+                add_y + row_height,
+                button_width,
+                cell_width * 0.5,
+            );
+        }
+    }
+
+    // The checkpoint whose row was clicked.
+    pub fn click_row(&self, x: f32, y: f32) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| row.click(x, y))
+            .map(|row| self.first() + row)
+    }
+
+    pub fn click_outside(&mut self, x: f32, y: f32) -> bool {
+        x < self.x || x > self.x + self.width || y < self.y || y > self.y + self.height
+    }
+}
@@ -0,0 +1,9 @@
+use macroquad::window::get_internal_gl;
+
+pub fn get_clipboard() -> Option<String> {
+    unsafe { get_internal_gl().quad_context.clipboard_get() }
+}
+
+pub fn set_clipboard(text: &str) {
+    unsafe { get_internal_gl().quad_context.clipboard_set(text) }
+}
@@ -1,12 +1,31 @@
 use std::cmp::min;
 
-use crate::board::Board;
+use crate::board::{Board, BoardMode};
+use crate::checkpoints_modal::CheckpointsModal;
+use crate::clipboard::{get_clipboard, set_clipboard};
+use crate::daily::{daily_seed, today, DailyRecord, DAILY_DIFFICULTY, DAILY_SYMMETRY};
+use crate::export::{export_grid, export_line, export_state};
 use crate::fonts::font_context::FontContext;
-use crate::generate::{create_puzzle, generate_board};
+use crate::game_key::GameKey;
+use crate::game_over_modal::GameOverModal;
+use crate::generate::generate_puzzle;
+use crate::grade::grade_puzzle;
+use crate::hint::Hint;
+use crate::import::import_puzzle;
+use crate::import_modal::ImportModal;
 use crate::index::xy_to_index;
+use crate::key_bindings::{KeyAction, KeyBindings, KeyCombo, KeyScope};
+use crate::key_bindings_modal::KeyBindingsModal;
 use crate::menu::{is_menu_action_number, Menu, MenuActions};
 use crate::new_game_modal::NewGameModal;
+use crate::options_modal::OptionsModal;
+use crate::replay::Replay;
 use crate::save::{load, save};
+use crate::settings::Settings;
+use crate::solver::solve;
+use crate::statistics::{GameOutcome, Statistics};
+use crate::statistics_modal::StatisticsModal;
+use crate::symmetry::Symmetry;
 use crate::victory_modal::VictoryModal;
 
 use macroquad::prelude::*;
@@ -23,13 +42,21 @@ fn load_board_from_save() -> Option<Board> {
     None
 }
 
-fn generate_new_board(difficulty: u8) -> Board {
+const MAX_FRAME_TIME: f64 = 1.0;
+// Seconds without any input before the game pauses itself.
+const IDLE_TIME: f64 = 120.0;
+const SAVE_INTERVAL: f64 = 10.0;
+
+// Short enough to type back in to replay the same puzzle.
+fn random_seed() -> u64 {
+    (miniquad::date::now() * 1000.0) as u64 % 1_000_000_000
+}
+
+fn new_board(givens: &[[u8; 9]; 9], solution: &[[u8; 9]; 9]) -> Board {
     let mut board = Board::new();
+    board.solution = *solution;
 
-    let mut board_gen = [[0; 9]; 9];
-    generate_board(&mut board_gen);
-    create_puzzle(&mut board_gen, difficulty);
-    for (y, row) in board_gen.iter().enumerate() {
+    for (y, row) in givens.iter().enumerate() {
         for (x, col) in row.iter().enumerate() {
             board.cell_state[xy_to_index(x, y, 9)].set_initial_number(*col);
         }
@@ -39,6 +66,28 @@ fn generate_new_board(difficulty: u8) -> Board {
     board
 }
 
+fn generate_new_board(difficulty: u8, seed: u64, symmetry: Symmetry, minimal: bool) -> Board {
+    let puzzle = generate_puzzle(seed, difficulty, symmetry, minimal);
+    let mut board = new_board(&puzzle.givens, &puzzle.solution);
+    board.seed = Some(seed);
+    board.difficulty = difficulty;
+    board.symmetry = symmetry;
+    board.minimal = minimal;
+
+    board
+}
+
+// Imported puzzles have no seed, the difficulty comes from grading them.
+fn import_new_board(puzzle: &[[u8; 9]; 9]) -> Board {
+    let mut solution = *puzzle;
+    solve(&mut solution);
+
+    let mut board = new_board(puzzle, &solution);
+    board.difficulty = grade_puzzle(puzzle).difficulty;
+
+    board
+}
+
 pub struct Context {
     pub font_context: FontContext,
     pub board: Board,
@@ -54,19 +103,42 @@ pub struct Context {
     pub old_width: u32,
     pub portrait: bool,
     pub new_game_modal: NewGameModal,
+    pub import_modal: ImportModal,
+    pub options_modal: OptionsModal,
+    pub checkpoints_modal: CheckpointsModal,
+    pub statistics_modal: StatisticsModal,
+    pub key_bindings_modal: KeyBindingsModal,
     pub victory_modal: VictoryModal,
+    pub game_over_modal: GameOverModal,
+    pub daily: DailyRecord,
+    pub settings: Settings,
+    pub key_bindings: KeyBindings,
+    pub hint: Option<Hint>,
+    pub paused: bool,
+    // Seconds since the last key, click or mouse move.
+    pub idle: f64,
+    pub last_mouse: (f32, f32),
+    // Shown once the keyboard moves it, cell first input always shows it.
+    pub cursor: bool,
+    pub dragging: bool,
+    pub statistics: Statistics,
+    pub replay: Option<Replay>,
 }
 
 impl Context {
     pub async fn new(font_path: &str, icon_font_path: &str) -> Self {
         let board_save = load_board_from_save();
-        let board = if let Some(board) = board_save {
+        let board = if let Some(mut board) = board_save {
+            // hints aren't saved, so their highlighting can't stay either
+            board.highlight();
+            board.ensure_solution();
+            board.migrate_history();
             board
         } else {
-            generate_new_board(2)
+            generate_new_board(2, random_seed(), Symmetry::None, false)
         };
 
-        let context = Context {
+        let mut context = Context {
             font_context: FontContext::new(font_path, icon_font_path).await,
             board,
             menu: Menu::new(),
@@ -81,9 +153,36 @@ impl Context {
             old_width: 0,
             portrait: true,
             new_game_modal: Default::default(),
+            import_modal: Default::default(),
+            options_modal: Default::default(),
+            checkpoints_modal: Default::default(),
+            statistics_modal: Default::default(),
+            key_bindings_modal: Default::default(),
             victory_modal: Default::default(),
+            game_over_modal: Default::default(),
+            daily: DailyRecord::load(),
+            settings: Settings::load(),
+            key_bindings: KeyBindings::load(),
+            hint: None,
+            paused: false,
+            idle: 0.0,
+            last_mouse: (0.0, 0.0),
+            cursor: false,
+            dragging: false,
+            statistics: Statistics::load(),
+            replay: None,
         };
 
+        // a lost game stays lost after a restart
+        if context.board.is_lost() {
+            context.game_over_modal.show();
+        }
+
+        // games from before there were statistics
+        if context.board.game_id.is_none() && !context.is_finished() {
+            context.start_game();
+        }
+
         context.save_board();
 
         context
@@ -94,25 +193,403 @@ impl Context {
         save("board", save_data.as_str());
     }
 
-    fn handle_input(&mut self) {
+    fn import(&mut self, text: &str) -> bool {
+        match import_puzzle(text) {
+            Ok(puzzle) => {
+                self.import_modal.hide();
+                self.set_board(import_new_board(&puzzle));
+                self.save_board();
+                true
+            }
+            Err(error) => {
+                self.import_modal.error = Some(error);
+                false
+            }
+        }
+    }
+
+    // Asking again while a hint is shown reveals more of it.
+    fn show_hint(&mut self) {
+        match &mut self.hint {
+            Some(hint) => hint.next_level(),
+            None => {
+                self.hint = Some(Hint::find(&self.board.cell_state));
+                self.board.hints += 1;
+            }
+        }
+
+        if let Some(hint) = &self.hint {
+            let (hint_cells, highlighted_cells) = hint.highlights();
+            self.board.highlight_hint(&hint_cells, &highlighted_cells);
+        }
+    }
+
+    fn clear_hint(&mut self) {
+        if self.hint.take().is_some() {
+            self.board.highlight();
+        }
+    }
+
+    fn add_checkpoint(&mut self) {
+        if self.board.add_checkpoint() {
+            self.save_board();
+        }
+    }
+
+    fn show_checkpoints(&mut self) {
+        self.checkpoints_modal
+            .show(self.board.history.checkpoints.len());
+    }
+
+    fn reveal(&mut self) {
+        self.board.disable_delete_mode();
+        if self.board.reveal() {
+            self.board.hints += 1;
+            if self.board.is_victory() {
+                self.win();
+            }
+            self.save_board();
+        }
+    }
+
+    fn fill_candidates(&mut self) {
+        self.clear_hint();
+        self.board.disable_delete_mode();
+        self.board.fill_candidates();
+        self.save_board();
+    }
+
+    fn is_modal_open(&self) -> bool {
+        self.victory_modal.show
+            || self.game_over_modal.show
+            || self.new_game_modal.show
+            || self.import_modal.show
+            || self.options_modal.show
+            || self.checkpoints_modal.show
+            || self.statistics_modal.show
+            || self.key_bindings_modal.show
+    }
+
+    fn is_finished(&self) -> bool {
+        self.board.is_victory() || self.board.is_lost()
+    }
+
+    // Longer frames mean the window was hidden or the game was suspended,
+    // so that time isn't counted. Macroquad doesn't tell when the window
+    // loses focus or is minimised, but no input arrives then either, so
+    // the game pauses itself after a while without any.
+    fn tick(&mut self) {
+        let seconds = get_frame_time() as f64;
+        if self.had_input() {
+            self.idle = 0.0;
+        } else {
+            self.idle += seconds;
+        }
+
+        if self.paused
+            || seconds > MAX_FRAME_TIME
+            || self.is_modal_open()
+            || self.replay.is_some()
+            || self.is_finished()
+        {
+            return;
+        }
+
+        if self.idle > IDLE_TIME {
+            self.toggle_pause();
+            return;
+        }
+
+        let before = self.board.elapsed;
+        self.board.elapsed += seconds;
+
+        // saved now and then as well as after every action
+        if (before / SAVE_INTERVAL) as u32 != (self.board.elapsed / SAVE_INTERVAL) as u32 {
+            self.save_board();
+        }
+    }
+
+    fn had_input(&mut self) -> bool {
+        let mouse = mouse_position();
+        let moved = mouse != self.last_mouse;
+        self.last_mouse = mouse;
+
+        moved
+            || get_last_key_pressed().is_some()
+            || is_mouse_button_down(MouseButton::Left)
+            || !touches().is_empty()
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.clear_hint();
+        self.save_board();
+    }
+
+    // Replaces the game, counting the old one as abandoned if it wasn't over.
+    fn set_board(&mut self, mut board: Board) {
+        self.finish_game(GameOutcome::Abandoned);
+        board.mistake_limit = self.settings.mistake_limit;
+        self.board = board;
+        self.start_game();
+        self.fill_new_candidates();
+    }
+
+    fn new_game(&mut self, key: GameKey) {
+        self.set_board(generate_new_board(
+            key.difficulty,
+            key.seed,
+            key.symmetry,
+            key.minimal,
+        ));
+        self.save_board();
+    }
+
+    fn show_new_game(&mut self) {
+        self.new_game_modal
+            .show(self.board.symmetry, self.board.minimal);
+    }
+
+    // The game typed on the new game screen, or a random one with its options.
+    fn new_game_from_modal(&mut self, difficulty: u8) {
+        let modal = &self.new_game_modal;
+        let key = modal.game_key(difficulty).unwrap_or(GameKey {
+            seed: random_seed(),
+            difficulty,
+            symmetry: modal.symmetry,
+            minimal: modal.minimal,
+        });
+        self.new_game(key);
+    }
+
+    fn daily_game(&mut self) {
+        let day = today();
+        let mut board =
+            generate_new_board(DAILY_DIFFICULTY, daily_seed(day), DAILY_SYMMETRY, false);
+        board.daily = Some(day);
+        self.set_board(board);
+        self.save_board();
+    }
+
+    fn fill_new_candidates(&mut self) {
+        if self.settings.auto_candidates {
+            self.board.fill_starting_candidates();
+        }
+    }
+
+    fn start_game(&mut self) {
+        let id = self.statistics.start(self.board.difficulty, today());
+        self.statistics.save_game(id);
+        self.board.game_id = Some(id);
+    }
+
+    fn finish_game(&mut self, outcome: GameOutcome) {
+        let Some(id) = self.board.game_id else {
+            return;
+        };
+
+        let board = &self.board;
+        if self
+            .statistics
+            .finish(id, outcome, board.elapsed, board.mistakes, board.hints)
+        {
+            self.statistics.save_game(id);
+        }
+    }
+
+    fn win(&mut self) {
+        self.complete_daily();
+
+        let best = self
+            .statistics
+            .difficulty_stats(self.board.difficulty)
+            .best_seconds;
+        self.finish_game(GameOutcome::Won);
+        self.victory_modal
+            .show(best.is_none_or(|best| self.board.elapsed < best));
+    }
+
+    // The board shows each move in turn without the history moving, and is
+    // put back the way it was when the replay is closed.
+    fn start_replay(&mut self) {
+        self.clear_hint();
+        self.board.disable_delete_mode();
+
+        let replay = Replay::new(self.board.history.moves());
+        self.board
+            .show_node(self.board.history.current, replay.target());
+        self.replay = Some(replay);
+    }
+
+    fn close_replay(&mut self) {
+        if let Some(replay) = self.replay.take() {
+            self.board
+                .show_node(replay.target(), self.board.history.current);
+        }
+    }
+
+    // Escape closes the replay as it does every other screen, next to the
+    // replay's own key for it.
+    fn handle_replay_input(&mut self) {
+        let action = self.pressed_action(KeyScope::Replay);
+        if is_key_pressed(KeyCode::Escape) || action == Some(KeyAction::ReplayClose) {
+            self.close_replay();
+            return;
+        }
+
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+
+        let shown = replay.target();
+        replay.advance(get_frame_time());
+
         if is_mouse_button_pressed(MouseButton::Left) {
             let (mouse_x, mouse_y) = mouse_position();
+            if replay.close.click(mouse_x, mouse_y) {
+                self.close_replay();
+                return;
+            }
 
-            if let Some(menu_action) = self.menu.click(mouse_x, mouse_y) {
-                if is_menu_action_number(menu_action) {
-                    let number = menu_action as u8;
-                    if self.board.is_number_done(number) {
+            if let Some(step) = replay.scrub(mouse_x, mouse_y) {
+                replay.set_step(step);
+            } else if replay.start.click(mouse_x, mouse_y) {
+                replay.set_step(0);
+            } else if replay.back.click(mouse_x, mouse_y) {
+                replay.set_step(replay.step.saturating_sub(1));
+            } else if replay.play.click(mouse_x, mouse_y) {
+                replay.toggle_play();
+            } else if replay.next.click(mouse_x, mouse_y) {
+                replay.set_step(replay.step + 1);
+            } else if replay.end.click(mouse_x, mouse_y) {
+                replay.set_step(replay.moves.len());
+            }
+        }
+
+        match action {
+            Some(KeyAction::ReplayPlay) => replay.toggle_play(),
+            Some(KeyAction::ReplayBack) => replay.set_step(replay.step.saturating_sub(1)),
+            Some(KeyAction::ReplayNext) => replay.set_step(replay.step + 1),
+            Some(KeyAction::ReplayStart) => replay.set_step(0),
+            Some(KeyAction::ReplayEnd) => replay.set_step(replay.moves.len()),
+            _ => {}
+        }
+
+        if replay.target() != shown {
+            let target = replay.target();
+            self.board.show_node(shown, target);
+        }
+    }
+
+    // While waiting for a key, the next one pressed is bound unless it's
+    // escape, which stays free for backing out.
+    fn handle_key_bindings_input(&mut self) {
+        let modal = &mut self.key_bindings_modal;
+        let key_pressed = get_last_key_pressed();
+        if let Some(action) = modal.waiting {
+            match key_pressed {
+                Some(KeyCode::Escape) => {
+                    modal.waiting = None;
+                    modal.error = None;
+                }
+                // modifiers on their own wait for the key they go with
+                Some(key) => {
+                    let Some(combo) = KeyCombo::pressed(key) else {
                         return;
+                    };
+                    let bound = if modal.adding {
+                        self.key_bindings.add(action, combo)
+                    } else {
+                        self.key_bindings.bind(action, combo)
+                    };
+                    match bound {
+                        Ok(()) => {
+                            self.key_bindings.save();
+                            modal.waiting = None;
+                            modal.error = None;
+                        }
+                        Err(error) => modal.error = Some(error),
                     }
+                }
+                None => {}
+            }
+            return;
+        }
 
-                    self.board.disable_delete_mode();
-                    self.board.set_selected_number(number);
-                    self.board.highlight();
-                    self.save_board();
+        if key_pressed == Some(KeyCode::Escape) {
+            modal.hide();
+            return;
+        }
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let (mouse_x, mouse_y) = mouse_position();
+            if modal.click_outside(mouse_x, mouse_y) {
+                modal.hide();
+                return;
+            }
+
+            modal.error = None;
+            if modal.previous.click(mouse_x, mouse_y) {
+                modal.previous_page();
+            } else if modal.next.click(mouse_x, mouse_y) {
+                modal.next_page();
+            } else if modal.reset.click(mouse_x, mouse_y) {
+                self.key_bindings.reset();
+                self.key_bindings.save();
+            } else if let Some(action) = modal.click_add(mouse_x, mouse_y) {
+                modal.waiting = Some(action);
+                modal.adding = true;
+            } else if let Some(action) = modal.click_row(mouse_x, mouse_y) {
+                modal.waiting = Some(action);
+                modal.adding = false;
+            }
+        }
+    }
+
+    // What the key pressed just now does on the screen.
+    fn pressed_action(&self, scope: KeyScope) -> Option<KeyAction> {
+        get_last_key_pressed()
+            .and_then(KeyCombo::pressed)
+            .and_then(|combo| self.key_bindings.action(scope, combo))
+    }
+
+    fn complete_daily(&mut self) {
+        if let Some(day) = self.board.daily {
+            if self.daily.complete(day, self.board.elapsed) {
+                self.daily.save();
+            }
+        }
+    }
+
+    fn handle_input(&mut self) {
+        if self.paused {
+            // anything goes back to the game, and is used up doing it
+            let key_pressed = get_last_key_pressed();
+            get_char_pressed();
+            if is_mouse_button_pressed(MouseButton::Left) || key_pressed.is_some() {
+                self.toggle_pause();
+            }
+            return;
+        }
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let (mouse_x, mouse_y) = mouse_position();
+            self.cursor = false;
+
+            if let Some(menu_action) = self.menu.click(mouse_x, mouse_y) {
+                if menu_action != MenuActions::Hint {
+                    self.clear_hint();
+                }
+
+                if is_menu_action_number(menu_action) {
+                    self.choose_number(menu_action as u8, false);
                 } else if menu_action == MenuActions::Pencil {
                     self.board.disable_delete_mode();
                     self.board.toggle_pencil_mode();
                     self.save_board();
+                } else if menu_action == MenuActions::Delete && self.settings.cell_first {
+                    self.board.delete_selected(&self.settings);
+                    self.save_board();
                 } else if menu_action == MenuActions::Delete {
                     self.board.toggle_delete_mode();
                     self.save_board();
@@ -120,55 +597,437 @@ impl Context {
                     self.board.disable_delete_mode();
                     self.board.undo();
                     self.save_board();
+                } else if menu_action == MenuActions::Redo {
+                    self.board.disable_delete_mode();
+                    self.board.redo();
+                    self.save_board();
+                } else if menu_action == MenuActions::Check {
+                    self.board.check();
+                    self.save_board();
+                } else if menu_action == MenuActions::Reveal {
+                    self.reveal();
+                } else if menu_action == MenuActions::Hint {
+                    self.show_hint();
+                } else if menu_action == MenuActions::Pause {
+                    self.toggle_pause();
+                } else if menu_action == MenuActions::Options {
+                    self.options_modal.show();
                 } else if menu_action == MenuActions::New {
-                    self.new_game_modal.show();
+                    self.show_new_game();
                 }
                 return;
             }
 
-            self.board.click(mouse_x, mouse_y);
-            if self.board.is_victory() {
-                self.victory_modal.show();
+            self.clear_hint();
+
+            // picks cells without putting anything in them, whichever the input mode
+            let control = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+            let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+            if control || shift {
+                if let Some(index) = self.board.cell_at(mouse_x, mouse_y) {
+                    self.board.disable_delete_mode();
+                    self.board.toggle_selected(index);
+                    return;
+                }
             }
-            self.save_board();
+
+            let mistakes = self.board.mistakes;
+            self.board.click(mouse_x, mouse_y, &self.settings);
+            self.after_entry(mistakes);
+
+            // with cell first input, dragging from the cell selects the ones passed over
+            self.dragging = self.settings.cell_first && self.board.selected_index.is_some();
+        } else if self.dragging && is_mouse_button_down(MouseButton::Left) {
+            let (mouse_x, mouse_y) = mouse_position();
+            if let Some(index) = self.board.cell_at(mouse_x, mouse_y) {
+                if self.board.selected_index != Some(index) {
+                    self.board.extend_selection(index);
+                }
+            }
+        } else {
+            self.dragging = false;
         }
 
+        // digits are read as keys, so shift can be told apart
+        while get_char_pressed().is_some() {}
+
         let key_pressed = get_last_key_pressed();
-        if let Some(key) = key_pressed {
-            if key == KeyCode::U {
+        if let Some(combo) = key_pressed.and_then(KeyCombo::pressed) {
+            let key = combo.key;
+            let shift = combo.shift;
+            let action = self.key_bindings.action(KeyScope::Game, combo);
+            if action != Some(KeyAction::Hint) {
+                self.clear_hint();
+            }
+
+            if action == Some(KeyAction::Redo) {
+                self.board.disable_delete_mode();
+                self.board.redo();
+                self.save_board();
+            } else if key == KeyCode::Escape {
+                self.cursor = false;
+                self.board.selected_cells.clear();
+                self.board.disable_delete_mode();
+            } else if let Some(number) = action.and_then(KeyAction::number) {
+                self.choose_number(number, shift);
+            } else if action == Some(KeyAction::Up) {
+                self.move_cursor(0, -1, shift);
+            } else if action == Some(KeyAction::Down) {
+                self.move_cursor(0, 1, shift);
+            } else if action == Some(KeyAction::Left) {
+                self.move_cursor(-1, 0, shift);
+            } else if action == Some(KeyAction::Right) {
+                self.move_cursor(1, 0, shift);
+            } else if action == Some(KeyAction::Clear) {
+                self.delete_at_cursor();
+            } else if action == Some(KeyAction::PencilMode) {
+                self.board.disable_delete_mode();
+                self.board.toggle_pencil_mode();
+                self.save_board();
+            } else if action == Some(KeyAction::NewGame) {
+                self.show_new_game();
+            } else if action == Some(KeyAction::Undo) {
                 self.board.disable_delete_mode();
                 self.board.undo();
                 self.save_board();
+            } else if action == Some(KeyAction::Check) {
+                self.board.check();
+                self.save_board();
+            } else if action == Some(KeyAction::Reveal) {
+                self.reveal();
+            } else if action == Some(KeyAction::Hint) {
+                self.show_hint();
+            } else if action == Some(KeyAction::CopyState) {
+                set_clipboard(&export_state(&self.board));
+            } else if action == Some(KeyAction::AddCheckpoint) {
+                self.add_checkpoint();
+            } else if action == Some(KeyAction::Checkpoints) {
+                self.show_checkpoints();
+            } else if action == Some(KeyAction::Statistics) {
+                self.statistics_modal.show();
+            } else if action == Some(KeyAction::Pause) {
+                self.toggle_pause();
+            } else if action == Some(KeyAction::Options) {
+                self.options_modal.show();
             }
+        }
+    }
+
+    // Entries go into the cursor's cell, or all of the selected cells.
+    pub fn has_cursor(&self) -> bool {
+        self.settings.cell_first || self.cursor || !self.board.selected_cells.is_empty()
+    }
+
+    // The first move only brings the cursor up where the selection is,
+    // with shift held the cells moved over are added to the selection.
+    fn move_cursor(&mut self, dx: i32, dy: i32, extend: bool) {
+        self.board.disable_delete_mode();
+        if !self.has_cursor() {
+            self.board.move_cursor(0, 0);
+        } else if extend {
+            self.board.extend_cursor(dx, dy);
+        } else {
+            self.board.move_cursor(dx, dy);
+        }
+        self.cursor = true;
+        self.save_board();
+    }
+
+    fn delete_at_cursor(&mut self) {
+        if !self.has_cursor() {
             return;
         }
 
-        let char_pressed = get_char_pressed();
-        if let Some(key @ '1'..='9') = char_pressed {
-            let number = key as u8 - 48; // 48 = '0'
-            if self.board.is_number_done(number) {
-                return;
-            }
+        self.board.disable_delete_mode();
+        self.board.delete_selected(&self.settings);
+        self.save_board();
+    }
 
-            self.board.disable_delete_mode();
-            self.board.set_selected_number(number);
-            self.board.highlight();
-            self.save_board();
+    // Picks the number to click cells with, or with the cursor puts it
+    // straight into the cell under it, as a pencil mark if asked to.
+    fn choose_number(&mut self, number: u8, pencil: bool) {
+        if self.board.is_number_done(number) {
+            return;
+        }
+
+        self.board.disable_delete_mode();
+        if self.has_cursor() {
+            let mode = if pencil {
+                BoardMode::Pencil
+            } else {
+                self.board.mode
+            };
+            let mistakes = self.board.mistakes;
+            self.board.enter_number(number, mode, &self.settings);
+            self.after_entry(mistakes);
+            return;
+        }
+
+        self.board.set_selected_number(number);
+        self.board.highlight();
+        self.save_board();
+    }
+
+    fn after_entry(&mut self, mistakes: u32) {
+        if self.board.is_victory() {
+            self.win();
+        } else if self.board.mistakes > mistakes && self.board.is_lost() {
+            self.finish_game(GameOutcome::Lost);
+            self.game_over_modal.show();
         }
+        self.save_board();
     }
 
     pub fn update(&mut self) {
         let mut force_update = false;
+        self.tick();
 
         if self.victory_modal.show {
+            if is_key_pressed(KeyCode::Escape) {
+                self.victory_modal.hide();
+                return;
+            }
+
+            if self.pressed_action(KeyScope::GameEnd) == Some(KeyAction::NextGame) {
+                self.victory_modal.hide();
+                self.show_new_game();
+                return;
+            }
+
             if is_mouse_button_pressed(MouseButton::Left) {
                 let (mouse_x, mouse_y) = mouse_position();
                 if self.victory_modal.click_outside(mouse_x, mouse_y) {
                     self.victory_modal.hide();
                     return;
                 }
+
+                if self.victory_modal.same_difficulty.click(mouse_x, mouse_y) {
+                    force_update = true;
+                    self.victory_modal.hide();
+                    self.new_game(GameKey {
+                        seed: random_seed(),
+                        difficulty: self.board.difficulty,
+                        symmetry: self.board.symmetry,
+                        minimal: self.board.minimal,
+                    });
+                } else if self.victory_modal.choose.click(mouse_x, mouse_y) {
+                    self.victory_modal.hide();
+                    self.show_new_game();
+                } else if self.victory_modal.replay.click(mouse_x, mouse_y) {
+                    force_update = true;
+                    self.victory_modal.hide();
+                    self.start_replay();
+                }
+            }
+        } else if self.game_over_modal.show {
+            // the game is over, so there is nothing to go back to
+            let (mouse_x, mouse_y) = mouse_position();
+            let clicked = is_mouse_button_pressed(MouseButton::Left);
+            let action = self.pressed_action(KeyScope::GameEnd);
+            if action == Some(KeyAction::Retry)
+                || (clicked && self.game_over_modal.retry.click(mouse_x, mouse_y))
+            {
+                force_update = true;
+                self.game_over_modal.hide();
+                self.clear_hint();
+                self.board.restart();
+                self.start_game();
+                self.fill_new_candidates();
+                self.save_board();
+            } else if action == Some(KeyAction::NextGame)
+                || (clicked && self.game_over_modal.new_game.click(mouse_x, mouse_y))
+            {
+                self.game_over_modal.hide();
+                self.show_new_game();
+            }
+        } else if self.statistics_modal.show {
+            if is_key_pressed(KeyCode::Escape) {
+                self.statistics_modal.hide();
+                return;
+            }
+
+            if is_mouse_button_pressed(MouseButton::Left) {
+                let (mouse_x, mouse_y) = mouse_position();
+                if self.statistics_modal.click_outside(mouse_x, mouse_y) {
+                    self.statistics_modal.hide();
+                }
+            }
+        } else if self.key_bindings_modal.show {
+            self.handle_key_bindings_input();
+        } else if self.checkpoints_modal.show {
+            if is_key_pressed(KeyCode::Escape) {
+                self.checkpoints_modal.hide();
+                return;
+            }
+
+            if is_mouse_button_pressed(MouseButton::Left) {
+                let (mouse_x, mouse_y) = mouse_position();
+                if self.checkpoints_modal.click_outside(mouse_x, mouse_y) {
+                    self.checkpoints_modal.hide();
+                    return;
+                }
+
+                let paged = self.checkpoints_modal.page_count() > 1;
+                if self.checkpoints_modal.add.click(mouse_x, mouse_y) {
+                    self.add_checkpoint();
+                    self.show_checkpoints();
+                } else if paged && self.checkpoints_modal.previous.click(mouse_x, mouse_y) {
+                    self.checkpoints_modal.previous_page();
+                } else if paged && self.checkpoints_modal.next.click(mouse_x, mouse_y) {
+                    self.checkpoints_modal.next_page();
+                } else if let Some(checkpoint) = self.checkpoints_modal.click_row(mouse_x, mouse_y)
+                {
+                    self.checkpoints_modal.hide();
+                    self.clear_hint();
+                    self.board.disable_delete_mode();
+                    self.board.go_to_checkpoint(checkpoint);
+                    self.save_board();
+                }
+            }
+        } else if self.options_modal.show {
+            if is_key_pressed(KeyCode::Escape) {
+                self.options_modal.hide();
+                return;
+            }
+
+            if is_mouse_button_pressed(MouseButton::Left) {
+                let (mouse_x, mouse_y) = mouse_position();
+                if self.options_modal.click_outside(mouse_x, mouse_y) {
+                    self.options_modal.hide();
+                    return;
+                }
+
+                if self.options_modal.key_bindings.click(mouse_x, mouse_y) {
+                    self.options_modal.hide();
+                    self.key_bindings_modal.show();
+                    return;
+                }
+
+                if self.options_modal.statistics.click(mouse_x, mouse_y) {
+                    self.options_modal.hide();
+                    self.statistics_modal.show();
+                    return;
+                }
+
+                if self.options_modal.checkpoints.click(mouse_x, mouse_y) {
+                    self.options_modal.hide();
+                    self.show_checkpoints();
+                    return;
+                }
+
+                if self.options_modal.mistake_limit.click(mouse_x, mouse_y) {
+                    self.settings.next_mistake_limit();
+                    self.settings.save();
+                    return;
+                }
+
+                if self.options_modal.fill_candidates.click(mouse_x, mouse_y) {
+                    self.options_modal.hide();
+                    self.fill_candidates();
+                    return;
+                }
+
+                // turning it on fills in the candidates so there is something to keep in sync
+                if self.options_modal.auto_candidates.click(mouse_x, mouse_y) {
+                    self.settings.auto_candidates = !self.settings.auto_candidates;
+                    self.settings.save();
+                    if self.settings.auto_candidates {
+                        self.fill_candidates();
+                    }
+                    return;
+                }
+
+                if self.options_modal.input_mode.click(mouse_x, mouse_y) {
+                    self.settings.cell_first = !self.settings.cell_first;
+                    self.settings.save();
+                    self.board.disable_delete_mode();
+                    return;
+                }
+
+                if self.options_modal.free_entry.click(mouse_x, mouse_y) {
+                    self.settings.free_entry = !self.settings.free_entry;
+                    self.settings.save();
+                    return;
+                }
+
+                let text = if self.options_modal.copy_line.click(mouse_x, mouse_y) {
+                    Some(export_line(&self.board))
+                } else if self.options_modal.copy_grid.click(mouse_x, mouse_y) {
+                    Some(export_grid(&self.board))
+                } else if self.options_modal.copy_state.click(mouse_x, mouse_y) {
+                    Some(export_state(&self.board))
+                } else {
+                    None
+                };
+
+                if let Some(text) = text {
+                    set_clipboard(&text);
+                    self.options_modal.copied = true;
+                }
+            }
+        } else if self.import_modal.show {
+            self.import_modal.input.handle_input();
+
+            if is_key_pressed(KeyCode::Escape) {
+                self.import_modal.hide();
+                return;
+            }
+
+            if is_key_pressed(KeyCode::Enter) {
+                let text = self.import_modal.input.text.clone();
+                force_update = self.import(&text);
+            }
+
+            if is_mouse_button_pressed(MouseButton::Left) {
+                let (mouse_x, mouse_y) = mouse_position();
+                if self.import_modal.click_outside(mouse_x, mouse_y) {
+                    self.import_modal.hide();
+                    return;
+                }
+
+                // the clipboard is imported as is, so stray characters are reported
+                if self.import_modal.paste.click(mouse_x, mouse_y) {
+                    if let Some(text) = get_clipboard() {
+                        self.import_modal.input.clear();
+                        self.import_modal.input.paste(&text);
+                        force_update = self.import(&text);
+                    }
+                }
+
+                if self.import_modal.start.click(mouse_x, mouse_y) {
+                    let text = self.import_modal.input.text.clone();
+                    force_update = self.import(&text);
+                }
             }
         } else if self.new_game_modal.show {
+            self.new_game_modal.seed_input.handle_input();
+
+            if is_key_pressed(KeyCode::Escape) {
+                self.new_game_modal.hide();
+                return;
+            }
+
+            // the key only takes digits and dashes, so letters are free to pick with
+            let action = self.pressed_action(KeyScope::NewGame);
+            let difficulty = if action == Some(KeyAction::Easy) {
+                Some(1)
+            } else if action == Some(KeyAction::Medium) {
+                Some(2)
+            } else if action == Some(KeyAction::Hard) {
+                Some(3)
+            } else {
+                None
+            };
+            if let Some(difficulty) = difficulty {
+                force_update = true;
+                self.new_game_modal.hide();
+                self.new_game_from_modal(difficulty);
+            } else if action == Some(KeyAction::Daily) {
+                force_update = true;
+                self.new_game_modal.hide();
+                self.daily_game();
+            }
+
             if is_mouse_button_pressed(MouseButton::Left) {
                 let (mouse_x, mouse_y) = mouse_position();
                 if self.new_game_modal.click_outside(mouse_x, mouse_y) {
@@ -176,14 +1035,47 @@ impl Context {
                     return;
                 }
 
-                if let Some(difficulty) = self.new_game_modal.click(mouse_x, mouse_y) {
+                // phones have no keyboard, tapping the seed pastes it instead
+                if self.new_game_modal.seed.click(mouse_x, mouse_y) {
+                    if let Some(text) = get_clipboard() {
+                        self.new_game_modal.seed_input.clear();
+                        self.new_game_modal.seed_input.paste(&text);
+                    }
+                    return;
+                }
+
+                // a typed key decides these on its own
+                let keyed = self.new_game_modal.typed_key().is_some();
+                if !keyed && self.new_game_modal.symmetry_button.click(mouse_x, mouse_y) {
+                    self.new_game_modal.symmetry = self.new_game_modal.symmetry.next();
+                    return;
+                }
+
+                if !keyed && self.new_game_modal.minimal_button.click(mouse_x, mouse_y) {
+                    self.new_game_modal.minimal = !self.new_game_modal.minimal;
+                    return;
+                }
+
+                if self.new_game_modal.import.click(mouse_x, mouse_y) {
+                    self.new_game_modal.hide();
+                    self.import_modal.show();
+                    return;
+                }
+
+                if self.new_game_modal.daily.click(mouse_x, mouse_y) {
                     force_update = true;
                     self.new_game_modal.hide();
+                    self.daily_game();
+                }
 
-                    self.board = generate_new_board(difficulty);
-                    self.save_board();
+                if let Some(difficulty) = self.new_game_modal.click(mouse_x, mouse_y) {
+                    force_update = true;
+                    self.new_game_modal.hide();
+                    self.new_game_from_modal(difficulty);
                 }
             }
+        } else if self.replay.is_some() {
+            self.handle_replay_input();
         } else {
             self.handle_input();
         }
@@ -243,10 +1135,15 @@ impl Context {
             self.font_context.modal_difficulty_font_1.width,
             self.font_context.modal_difficulty_font_1.height,
         );
-        self.victory_modal.update(
-            self.game_square,
-            self.font_context.modal_difficulty_font_1.width,
-            self.font_context.modal_difficulty_font_1.height,
-        );
+        self.import_modal.update(self.game_square);
+        self.options_modal.update(self.game_square);
+        self.checkpoints_modal.update(self.game_square);
+        self.statistics_modal.update(self.game_square);
+        self.key_bindings_modal.update(self.game_square);
+        self.game_over_modal.update(self.game_square);
+        self.victory_modal.update(self.game_square);
+        if let Some(replay) = &mut self.replay {
+            replay.update(&self.menu);
+        }
     }
 }
@@ -0,0 +1,115 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::rng::Rng;
+use crate::save::{load, save};
+use crate::symmetry::Symmetry;
+
+pub const DAILY_DIFFICULTY: u8 = 2;
+pub const DAILY_SYMMETRY: Symmetry = Symmetry::Rotational;
+
+// Mixed into the day so daily seeds don't line up with small typed seeds.
+const DAILY_SALT: u64 = 0x5EED_DA11;
+
+// Days since the unix epoch in UTC, so everyone gets the same day at the same time.
+pub fn today() -> u32 {
+    (macroquad::miniquad::date::now() / 86400.0) as u32
+}
+
+pub fn daily_seed(day: u32) -> u64 {
+    Rng::new(DAILY_SALT ^ day as u64).next_u64() % 1_000_000_000
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DailyResult {
+    pub day: u32,
+    pub seconds: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct DailyRecord {
+    pub last_completed: Option<u32>,
+    pub streak: u32,
+    pub best_streak: u32,
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyRecord {
+    pub fn load() -> Self {
+        load("daily")
+            .and_then(|daily_str| serde_json::from_str(&daily_str).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let save_data = serde_json::to_string(self).unwrap();
+        save("daily", save_data.as_str());
+    }
+
+    // The streak only counts while yesterday's or today's daily is done.
+    pub fn current_streak(&self, day: u32) -> u32 {
+        match self.last_completed {
+            Some(last) if last + 1 >= day => self.streak,
+            _ => 0,
+        }
+    }
+
+    // Returns false if that day was already completed.
+    pub fn complete(&mut self, day: u32, seconds: f64) -> bool {
+        if self.results.iter().any(|result| result.day == day) {
+            return false;
+        }
+
+        self.streak = match self.last_completed {
+            Some(last) if last + 1 == day => self.streak + 1,
+            _ => 1,
+        };
+        self.best_streak = self.best_streak.max(self.streak);
+        self.last_completed = Some(day);
+        self.results.push(DailyResult { day, seconds });
+
+        true
+    }
+
+    pub fn result(&self, day: u32) -> Option<DailyResult> {
+        self.results
+            .iter()
+            .copied()
+            .find(|result| result.day == day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_per_day() {
+        assert_eq!(daily_seed(19000), daily_seed(19000));
+        assert_ne!(daily_seed(19000), daily_seed(19001));
+        assert!(daily_seed(19000) < 1_000_000_000);
+    }
+
+    #[test]
+    fn streak() {
+        let mut record = DailyRecord::default();
+        assert!(record.result(100).is_none());
+        assert_eq!(record.current_streak(100), 0);
+
+        assert!(record.complete(100, 120.0));
+        assert!(!record.complete(100, 60.0));
+        assert!(record.result(100).is_some());
+        assert_eq!(record.current_streak(100), 1);
+        assert_eq!(record.result(100).unwrap().seconds, 120.0);
+
+        assert!(record.complete(101, 90.0));
+        assert!(record.complete(102, 80.0));
+        assert_eq!(record.current_streak(102), 3);
+        assert_eq!(record.current_streak(103), 3);
+        assert_eq!(record.current_streak(104), 0);
+
+        assert!(record.complete(105, 70.0));
+        assert_eq!(record.streak, 1);
+        assert_eq!(record.best_streak, 3);
+        assert_eq!(record.results.len(), 4);
+    }
+}
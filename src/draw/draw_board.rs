@@ -1,6 +1,6 @@
 use macroquad::{
     prelude::{BLACK, GRAY},
-    shapes::{draw_line, draw_rectangle},
+    shapes::{draw_line, draw_rectangle, draw_rectangle_lines},
     text::draw_text_ex,
 };
 
@@ -9,33 +9,90 @@ use crate::{
     cell_location::CellLocation,
     cell_state::{CellSelection, CellState},
     context::Context,
-    fonts::font_types::{CellFont, CellPencilFont},
+    fonts::font_context::FontContext,
     index::index_to_xy,
-    CELL_COLOR_HIGHLIGHTED, CELL_COLOR_NORMAL, CELL_COLOR_NORMAL_EMPHASIZE,
-    CELL_COLOR_PENCIL_EMPHASIZE,
+    CELL_COLOR_CONFLICT, CELL_COLOR_HIGHLIGHTED, CELL_COLOR_HINT, CELL_COLOR_NORMAL,
+    CELL_COLOR_NORMAL_EMPHASIZE, CELL_COLOR_PENCIL_EMPHASIZE, CELL_CURSOR_COLOR,
 };
 
-pub fn draw_board(context: &Context) {
-    draw_board_cells(
-        &context.board,
-        &context.font_context.initial_font,
-        &context.font_context.font,
-        &context.font_context.pencil_font,
+// The numbers are hidden, so a pause can't be used to think for free.
+fn draw_paused(context: &Context) {
+    let board = &context.board;
+    draw_rectangle(
+        board.game_padding,
+        board.game_padding,
+        board.board_size,
+        board.board_size,
+        CELL_COLOR_NORMAL,
+    );
+
+    let center_x = board.game_padding + (board.board_size / 2.0);
+    let center_y = board.game_padding + (board.board_size / 2.0);
+    let font = &context.font_context.modal_text_font;
+    draw_text_ex(
+        "Paused",
+        center_x - (font.measure("Paused") / 2.0),
+        center_y,
+        font.params,
+    );
+
+    let small_font = &context.font_context.modal_small_text_font_faded;
+    let text = "Click or press a key to go on";
+    draw_text_ex(
+        text,
+        center_x - (small_font.measure(text) / 2.0),
+        center_y + (board.cell_size / 2.0) + small_font.height,
+        small_font.params,
     );
+}
+
+pub fn draw_board(context: &Context) {
+    if context.paused {
+        draw_paused(context);
+        return;
+    }
+
+    draw_board_cells(&context.board, &context.font_context);
 
     draw_cell_lines(context);
     draw_box_lines(context);
+    draw_cursor(context);
+}
+
+// The cells numbers go into with cell first input, the keyboard or a selection.
+fn draw_cursor(context: &Context) {
+    if !context.has_cursor() {
+        return;
+    }
+
+    let board = &context.board;
+    for index in board
+        .selected_cells
+        .iter()
+        .chain(board.selected_index.iter())
+    {
+        let location = &board.cell_location[*index];
+        draw_rectangle_lines(
+            location.x,
+            location.y,
+            location.size,
+            location.size,
+            location.size * 0.08,
+            CELL_CURSOR_COLOR,
+        );
+    }
 }
 
 fn draw_cell(
     cell_state: &CellState,
     cell_location: &CellLocation,
     mode: &BoardMode,
-    initial_font: &CellFont,
-    font: &CellFont,
-    pencil_font: &CellPencilFont,
+    conflict: bool,
+    font_context: &FontContext,
 ) {
-    let color = if cell_state.selection == CellSelection::Emphasized {
+    let color = if conflict {
+        CELL_COLOR_CONFLICT
+    } else if cell_state.selection == CellSelection::Emphasized {
         if *mode == BoardMode::Pencil {
             CELL_COLOR_PENCIL_EMPHASIZE
         } else {
@@ -43,6 +100,8 @@ fn draw_cell(
         }
     } else if cell_state.selection == CellSelection::Highlighted {
         CELL_COLOR_HIGHLIGHTED
+    } else if cell_state.selection == CellSelection::Hint {
+        CELL_COLOR_HINT
     } else {
         CELL_COLOR_NORMAL
     };
@@ -57,18 +116,23 @@ fn draw_cell(
 
     if cell_state.has_number() {
         if let Some(n) = cell_state.number {
+            let font = if cell_state.initial {
+                &font_context.initial_font
+            } else if cell_state.wrong {
+                &font_context.wrong_font
+            } else {
+                &font_context.font
+            };
+
             draw_text_ex(
                 n.to_string().as_str(),
                 cell_location.x + font.x_offset,
                 cell_location.y + font.y_offset,
-                if cell_state.initial {
-                    initial_font.params
-                } else {
-                    font.params
-                },
+                font.params,
             );
         }
     } else if cell_state.has_pencil() {
+        let pencil_font = &font_context.pencil_font;
         for (i, pencil) in cell_state.pencil.iter().enumerate() {
             if let Some(n) = pencil {
                 let (x, y) = index_to_xy(i, 3);
@@ -84,20 +148,15 @@ fn draw_cell(
     }
 }
 
-fn draw_board_cells(
-    board: &Board,
-    initial_font: &CellFont,
-    font: &CellFont,
-    pencil_font: &CellPencilFont,
-) {
-    for i in 0..81 {
+fn draw_board_cells(board: &Board, font_context: &FontContext) {
+    let conflicts = board.conflicts();
+    for (i, conflict) in conflicts.into_iter().enumerate() {
         draw_cell(
             &board.cell_state[i],
             &board.cell_location[i],
             &board.mode,
-            initial_font,
-            font,
-            pencil_font,
+            conflict,
+            font_context,
         );
     }
 }
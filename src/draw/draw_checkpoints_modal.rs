@@ -0,0 +1,61 @@
+use macroquad::{
+    prelude::WHITE,
+    shapes::draw_rectangle,
+    text::draw_text_ex,
+    window::{screen_height, screen_width},
+};
+
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_button};
+use crate::{context::Context, MODAL_BACKGROUND};
+
+pub fn draw_checkpoints_modal(context: &Context) {
+    if !context.checkpoints_modal.show {
+        return;
+    }
+
+    let modal = &context.checkpoints_modal;
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), MODAL_BACKGROUND);
+    draw_rounded_rectangle(modal.x, modal.y, modal.width, modal.height, 20.0, WHITE);
+
+    let font = &context.font_context.modal_text_font;
+    let paged = modal.page_count() > 1;
+    let title = if paged {
+        format!("Checkpoints {}/{}", modal.page + 1, modal.page_count())
+    } else {
+        "Checkpoints".to_string()
+    };
+    draw_text_ex(
+        &title,
+        modal.x + (modal.width / 2.0) - (font.measure(&title) / 2.0),
+        modal.y + (modal.title_height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+
+    let history = &context.board.history;
+    if modal.rows.is_empty() {
+        let small_font = &context.font_context.modal_small_text_font_faded;
+        let text = "No checkpoints yet";
+        draw_text_ex(
+            text,
+            modal.x + (modal.width / 2.0) - (small_font.measure(text) / 2.0),
+            modal.add.y - (modal.title_height / 2.0) + (small_font.height / 2.0),
+            small_font.params,
+        );
+    }
+
+    // the board is already at the faded one
+    for (row, checkpoint) in modal.rows.iter().zip(&history.checkpoints[modal.first()..]) {
+        let row_font = if checkpoint.node == history.current {
+            &context.font_context.modal_text_font_faded
+        } else {
+            font
+        };
+        draw_text_button(row, &checkpoint.name, row_font);
+    }
+
+    draw_text_button(&modal.add, "Add checkpoint", font);
+    if paged {
+        draw_text_button(&modal.previous, "Back", font);
+        draw_text_button(&modal.next, "Next", font);
+    }
+}
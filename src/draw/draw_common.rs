@@ -1,8 +1,11 @@
 use macroquad::{
     prelude::{vec2, Color},
     shapes::{draw_rectangle, draw_triangle},
+    text::{draw_text_ex, get_text_center, TextParams},
 };
 
+use crate::{fonts::font_types::TextFont, text_button::TextButton, MODAL_INPUT_BACKGROUND};
+
 fn draw_quarter_circle(center_x: f32, center_y: f32, radius: f32, angle: f32, color: Color) {
     const NUM_TRIANGLES: u32 = 10;
     const ANGLE_STEP: f32 = std::f32::consts::FRAC_PI_2 / NUM_TRIANGLES as f32;
@@ -38,3 +41,105 @@ pub fn draw_rounded_rectangle(x: f32, y: f32, width: f32, height: f32, radius: f
     draw_quarter_circle(x + width - radius, y + height - radius, radius, 0.0, color);
     draw_quarter_circle(x + radius, y + height - radius, radius, DEG90, color);
 }
+
+pub fn draw_text_button(button: &TextButton, text: &str, font: &TextFont) {
+    draw_rounded_rectangle(
+        button.x,
+        button.y,
+        button.width,
+        button.height,
+        10.0,
+        MODAL_INPUT_BACKGROUND,
+    );
+
+    draw_text_ex(
+        text,
+        button.x + (button.width / 2.0) - (font.measure(text) / 2.0),
+        button.y + (button.height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+}
+
+// Draws the text centered on the point, turned by the rotation in radians.
+pub fn draw_text_centered(text: &str, x: f32, y: f32, font: &TextFont, rotation: f32) {
+    let center = get_text_center(text, None, font.params.font_size, 1.0, rotation);
+    draw_text_ex(
+        text,
+        x - center.x,
+        y - center.y,
+        TextParams {
+            rotation,
+            ..font.params
+        },
+    );
+}
+
+// Breaks the text up at spaces so no line is wider than the width,
+// a single word that is too long still gets a line of its own.
+pub fn wrap_text(text: &str, font: &TextFont, width: f32) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for word in text.split(' ') {
+        if let Some(line) = lines.last_mut() {
+            let candidate = format!("{} {}", line, word);
+            if font.measure(&candidate) <= width {
+                *line = candidate;
+                continue;
+            }
+        }
+        lines.push(word.to_string());
+    }
+    lines
+}
+
+pub fn format_time(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            (seconds / 60) % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+pub fn difficulty_name(difficulty: u8) -> &'static str {
+    match difficulty {
+        1 => "Easy",
+        2 => "Medium",
+        _ => "Hard",
+    }
+}
+
+// "1 hint" but "2 hints".
+pub fn plural(count: u32, word: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, word)
+    } else {
+        format!("{} {}s", count, word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_time, plural};
+
+    #[test]
+    fn time_format() {
+        assert_eq!(format_time(0.0), "0:00");
+        assert_eq!(format_time(-5.0), "0:00");
+        assert_eq!(format_time(59.9), "0:59");
+        assert_eq!(format_time(61.0), "1:01");
+        assert_eq!(format_time(3600.0), "1:00:00");
+        assert_eq!(format_time(3725.0), "1:02:05");
+    }
+
+    #[test]
+    fn plural_words() {
+        assert_eq!(plural(0, "hint"), "0 hints");
+        assert_eq!(plural(1, "hint"), "1 hint");
+        assert_eq!(plural(2, "mistake"), "2 mistakes");
+    }
+}
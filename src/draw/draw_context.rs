@@ -2,15 +2,35 @@ use macroquad::prelude::*;
 
 use crate::context::Context;
 use crate::draw::draw_board::draw_board;
+use crate::draw::draw_checkpoints_modal::draw_checkpoints_modal;
+use crate::draw::draw_game_over_modal::draw_game_over_modal;
+use crate::draw::draw_hint::draw_hint;
+use crate::draw::draw_import_modal::draw_import_modal;
+use crate::draw::draw_key_bindings_modal::draw_key_bindings_modal;
 use crate::draw::draw_menu::draw_menu;
 use crate::draw::draw_new_game_modal::draw_new_game_modal;
+use crate::draw::draw_options_modal::draw_options_modal;
+use crate::draw::draw_replay::draw_replay;
+use crate::draw::draw_statistics_modal::draw_statistics_modal;
 use crate::draw::draw_victory_modal::draw_victory_modal;
 
 pub fn draw_context(context: &Context) {
     clear_background(WHITE);
 
     draw_board(context);
-    draw_menu(context);
+    draw_hint(context);
+    // the replay controls take the menu's place
+    if context.replay.is_some() {
+        draw_replay(context);
+    } else {
+        draw_menu(context);
+    }
     draw_new_game_modal(context);
+    draw_import_modal(context);
+    draw_options_modal(context);
+    draw_checkpoints_modal(context);
+    draw_statistics_modal(context);
+    draw_key_bindings_modal(context);
     draw_victory_modal(context);
+    draw_game_over_modal(context);
 }
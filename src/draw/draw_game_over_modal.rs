@@ -0,0 +1,39 @@
+use macroquad::{
+    prelude::WHITE,
+    shapes::draw_rectangle,
+    text::draw_text_ex,
+    window::{screen_height, screen_width},
+};
+
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_button};
+use crate::{context::Context, MODAL_BACKGROUND};
+
+pub fn draw_game_over_modal(context: &Context) {
+    if !context.game_over_modal.show {
+        return;
+    }
+
+    let modal = &context.game_over_modal;
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), MODAL_BACKGROUND);
+    draw_rounded_rectangle(modal.x, modal.y, modal.width, modal.height, 20.0, WHITE);
+
+    let font = &context.font_context.modal_text_font;
+    draw_text_ex(
+        "Game over",
+        modal.x + (modal.width / 2.0) - (font.measure("Game over") / 2.0),
+        modal.y + (modal.title_height * 0.35) + (font.height / 2.0),
+        font.params,
+    );
+
+    let small_font = &context.font_context.modal_small_text_font_faded;
+    let mistakes = format!("{} mistakes", context.board.mistakes);
+    draw_text_ex(
+        &mistakes,
+        modal.x + (modal.width / 2.0) - (small_font.measure(&mistakes) / 2.0),
+        modal.y + (modal.title_height * 0.7) + (small_font.height / 2.0),
+        small_font.params,
+    );
+
+    draw_text_button(&modal.retry, "Try again", font);
+    draw_text_button(&modal.new_game, "New game", font);
+}
@@ -0,0 +1,44 @@
+use macroquad::text::draw_text_ex;
+
+use crate::cell_state::CellSelection;
+use crate::draw::draw_common::{draw_rounded_rectangle, wrap_text};
+use crate::{context::Context, HINT_BACKGROUND};
+
+pub fn draw_hint(context: &Context) {
+    let Some(hint) = &context.hint else {
+        return;
+    };
+
+    let font = &context.font_context.modal_small_text_font;
+    let cell_size = context.board.cell_size;
+    let width = context.board.board_size - cell_size;
+    let lines = wrap_text(&hint.message(), font, width - (cell_size * 0.5));
+
+    let line_height = font.height * 1.6;
+    let height = (lines.len() as f32 * line_height) + (cell_size * 0.3);
+
+    // keep out of the way of the cells the hint points at
+    let hint_on_top = context.board.cell_state[..27]
+        .iter()
+        .any(|cell| cell.selection == CellSelection::Hint);
+    let x = context.game_padding + (cell_size / 2.0);
+    let y = if hint_on_top {
+        context.game_padding + context.board.board_size - height - (cell_size * 0.2)
+    } else {
+        context.game_padding + (cell_size * 0.2)
+    };
+
+    draw_rounded_rectangle(x, y, width, height, 10.0, HINT_BACKGROUND);
+
+    for (i, line) in lines.iter().enumerate() {
+        draw_text_ex(
+            line,
+            x + (width / 2.0) - (font.measure(line) / 2.0),
+            y + (cell_size * 0.15)
+                + (i as f32 * line_height)
+                + (line_height / 2.0)
+                + (font.height / 2.0),
+            font.params,
+        );
+    }
+}
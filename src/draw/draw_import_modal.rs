@@ -0,0 +1,121 @@
+use macroquad::{
+    prelude::{BLACK, GRAY, WHITE},
+    shapes::{draw_line, draw_rectangle},
+    text::draw_text_ex,
+    window::{screen_height, screen_width},
+};
+
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_button};
+use crate::{context::Context, MODAL_BACKGROUND, MODAL_INPUT_BACKGROUND};
+
+// Shows the typed characters as a small board so it's easy to follow
+// which cell comes next.
+fn draw_preview(context: &Context) {
+    let modal = &context.import_modal;
+    let font = &context.font_context.modal_small_text_font;
+    let cell_size = modal.grid_size / 9.0;
+
+    let typed = modal.input.text.chars().count();
+    if typed < 81 {
+        draw_rectangle(
+            modal.grid_x + ((typed % 9) as f32 * cell_size),
+            modal.grid_y + ((typed / 9) as f32 * cell_size),
+            cell_size,
+            cell_size,
+            MODAL_INPUT_BACKGROUND,
+        );
+    }
+
+    for (index, character) in modal.input.text.chars().enumerate() {
+        if character == '0' || character == '.' {
+            continue;
+        }
+
+        let text = character.to_string();
+        draw_text_ex(
+            &text,
+            modal.grid_x + ((index % 9) as f32 * cell_size) + (cell_size / 2.0)
+                - (font.measure(&text) / 2.0),
+            modal.grid_y
+                + ((index / 9) as f32 * cell_size)
+                + (cell_size / 2.0)
+                + (font.height / 2.0),
+            font.params,
+        );
+    }
+
+    for line in 0..10 {
+        let (width, color) = if line % 3 == 0 {
+            (2.0, BLACK)
+        } else {
+            (1.0, GRAY)
+        };
+        let offset = line as f32 * cell_size;
+
+        draw_line(
+            modal.grid_x + offset,
+            modal.grid_y,
+            modal.grid_x + offset,
+            modal.grid_y + modal.grid_size,
+            width,
+            color,
+        );
+        draw_line(
+            modal.grid_x,
+            modal.grid_y + offset,
+            modal.grid_x + modal.grid_size,
+            modal.grid_y + offset,
+            width,
+            color,
+        );
+    }
+}
+
+fn draw_message(context: &Context) {
+    let modal = &context.import_modal;
+    let (text, font) = match modal.error {
+        Some(error) => (error.to_string(), &context.font_context.modal_error_font),
+        None => (
+            "Type or paste a puzzle".to_string(),
+            &context.font_context.modal_small_text_font_faded,
+        ),
+    };
+
+    draw_text_ex(
+        &text,
+        modal.x + (modal.width / 2.0) - (font.measure(&text) / 2.0),
+        modal.message_y + (font.height / 2.0),
+        font.params,
+    );
+}
+
+pub fn draw_import_modal(context: &Context) {
+    if !context.import_modal.show {
+        return;
+    }
+
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), MODAL_BACKGROUND);
+    draw_rounded_rectangle(
+        context.import_modal.x,
+        context.import_modal.y,
+        context.import_modal.width,
+        context.import_modal.height,
+        20.0,
+        WHITE,
+    );
+
+    let font = &context.font_context.modal_text_font;
+    draw_text_ex(
+        "Import",
+        context.import_modal.x + (context.import_modal.width / 2.0)
+            - (font.measure("Import") / 2.0),
+        context.import_modal.y + (context.import_modal.title_height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+
+    draw_preview(context);
+    draw_message(context);
+
+    draw_text_button(&context.import_modal.paste, "Paste", font);
+    draw_text_button(&context.import_modal.start, "Start", font);
+}
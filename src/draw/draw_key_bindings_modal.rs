@@ -0,0 +1,111 @@
+use macroquad::{
+    prelude::WHITE,
+    shapes::draw_rectangle,
+    text::draw_text_ex,
+    window::{screen_height, screen_width},
+};
+
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_button};
+use crate::{context::Context, MODAL_BACKGROUND, MODAL_INPUT_BACKGROUND};
+
+fn draw_message(context: &Context) {
+    let modal = &context.key_bindings_modal;
+    let (text, font) = match (modal.error, modal.waiting) {
+        (Some(error), _) => (error.to_string(), &context.font_context.modal_error_font),
+        (None, Some(action)) if modal.adding => (
+            format!("Press a key to add to {}", action.name()),
+            &context.font_context.modal_small_text_font,
+        ),
+        (None, Some(action)) => (
+            format!("Press a key for {}", action.name()),
+            &context.font_context.modal_small_text_font,
+        ),
+        (None, None) => (
+            "Click an action to change its key, + to add one".to_string(),
+            &context.font_context.modal_small_text_font_faded,
+        ),
+    };
+
+    draw_text_ex(
+        &text,
+        modal.x + (modal.width / 2.0) - (font.measure(&text) / 2.0),
+        modal.message_y + (font.height / 2.0),
+        font.params,
+    );
+}
+
+// The action's name on the left and its keys on the right.
+fn draw_rows(context: &Context) {
+    let modal = &context.key_bindings_modal;
+    let font = &context.font_context.menu_text_font;
+    let faded_font = &context.font_context.modal_small_text_font_faded;
+
+    for ((row, add), action) in modal
+        .rows
+        .iter()
+        .zip(modal.adds.iter())
+        .zip(modal.actions())
+    {
+        draw_rounded_rectangle(
+            row.x,
+            row.y,
+            row.width,
+            row.height,
+            10.0,
+            MODAL_INPUT_BACKGROUND,
+        );
+
+        let padding = row.height * 0.3;
+        let text_y = row.y + (row.height / 2.0) + (font.height / 2.0);
+        draw_text_ex(action.name(), row.x + padding, text_y, font.params);
+
+        let keys: Vec<String> = context
+            .key_bindings
+            .keys(*action)
+            .iter()
+            .filter_map(|combo| combo.name())
+            .collect();
+        let (keys, keys_font) = if modal.waiting == Some(*action) {
+            ("...".to_string(), faded_font)
+        } else if keys.is_empty() {
+            ("None".to_string(), faded_font)
+        } else {
+            (keys.join(", "), font)
+        };
+        draw_text_ex(
+            &keys,
+            row.x + row.width - padding - keys_font.measure(&keys),
+            text_y,
+            keys_font.params,
+        );
+
+        draw_text_button(add, "+", font);
+    }
+}
+
+pub fn draw_key_bindings_modal(context: &Context) {
+    if !context.key_bindings_modal.show {
+        return;
+    }
+
+    let modal = &context.key_bindings_modal;
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), MODAL_BACKGROUND);
+    draw_rounded_rectangle(modal.x, modal.y, modal.width, modal.height, 20.0, WHITE);
+
+    let font = &context.font_context.modal_text_font;
+    let title = format!("Key bindings {}/{}", modal.page + 1, modal.page_count());
+    draw_text_ex(
+        &title,
+        modal.x + (modal.width / 2.0) - (font.measure(&title) / 2.0),
+        modal.y + (modal.title_height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+
+    draw_rows(context);
+
+    draw_text_button(&modal.previous, "Back", font);
+    draw_text_button(&modal.next, "Next", font);
+    draw_text_button(&modal.reset, "Reset", font);
+
+    draw_message(context);
+}
@@ -1,6 +1,8 @@
 use macroquad::text::draw_text_ex;
 
-use crate::draw::draw_common::draw_rounded_rectangle;
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_centered, format_time};
+use crate::key_bindings::KeyAction;
+use crate::menu_item::MenuItem;
 use crate::{
     board::BoardMode, context::Context, ICON_DELETE, ICON_NEW, ICON_PENCIL, ICON_PENCIL_SLASH,
     ICON_UNDO, MENU_DELETE_BACKGROUND, MENU_NUMBER_BACKGROUND_NORMAL,
@@ -48,6 +50,37 @@ fn draw_menu_new(context: &Context, icon_x_offset: f32, icon_y_offset: f32) {
     );
 }
 
+// For the actions the icon font has no icon for, with their key underneath.
+fn draw_menu_label(context: &Context, item: &MenuItem, text: &str, action: KeyAction) {
+    let font = &context.font_context.menu_text_font;
+    let center_x = item.x + (context.menu.item_size / 2.0);
+    let center_y = item.y + (context.menu.item_size / 2.0);
+
+    let Some(key) = context.key_bindings.shortcut(action) else {
+        draw_text_ex(
+            text,
+            center_x - (font.measure(text) / 2.0),
+            center_y + (font.height / 2.0),
+            font.params,
+        );
+        return;
+    };
+
+    draw_text_ex(
+        text,
+        center_x - (font.measure(text) / 2.0),
+        center_y,
+        font.params,
+    );
+    let key_font = &context.font_context.menu_text_font_faded;
+    draw_text_ex(
+        &key,
+        center_x - (key_font.measure(&key) / 2.0),
+        center_y + (key_font.height * 1.25),
+        key_font.params,
+    );
+}
+
 fn draw_menu_delete(context: &Context, icon_x_offset: f32, icon_y_offset: f32) {
     if !context.board.delete_mode {
         draw_text_ex(
@@ -127,8 +160,37 @@ fn draw_menu_numbers(context: &Context) {
     }
 }
 
+fn draw_menu_status(context: &Context) {
+    let mistakes = match context.board.mistake_limit {
+        Some(limit) => format!("Mistakes {}/{}", context.board.mistakes, limit),
+        None => format!("Mistakes {}", context.board.mistakes),
+    };
+    let time = format_time(context.board.elapsed);
+    let text = if context.paused {
+        format!("Paused {}    {}", time, mistakes)
+    } else {
+        format!("{}    {}", time, mistakes)
+    };
+
+    // the strip runs down the side of the landscape menu
+    let rotation = if context.menu.portrait {
+        0.0
+    } else {
+        std::f32::consts::FRAC_PI_2
+    };
+
+    draw_text_centered(
+        &text,
+        context.menu.status_x + (context.menu.status_width / 2.0),
+        context.menu.status_y + (context.menu.status_height / 2.0),
+        &context.font_context.menu_text_font,
+        rotation,
+    );
+}
+
 pub fn draw_menu(context: &Context) {
     draw_menu_numbers(context);
+    draw_menu_status(context);
 
     let icon_x_offset = context.font_context.icon_font.width / 2.0; //  (context.font_context.icon_font.width - context.menu.pencil.size).abs() / 2.0;
     let icon_y_offset =
@@ -137,5 +199,10 @@ pub fn draw_menu(context: &Context) {
     draw_menu_pencil(context, icon_x_offset, icon_y_offset);
     draw_menu_undo(context, icon_x_offset, icon_y_offset);
     draw_menu_delete(context, icon_x_offset, icon_y_offset);
+    draw_menu_label(context, &context.menu.hint, "Hint", KeyAction::Hint);
+    draw_menu_label(context, &context.menu.check, "Check", KeyAction::Check);
+    draw_menu_label(context, &context.menu.reveal, "Reveal", KeyAction::Reveal);
+    draw_menu_label(context, &context.menu.redo, "Redo", KeyAction::Redo);
+    draw_menu_label(context, &context.menu.options, "More", KeyAction::Options);
     draw_menu_new(context, icon_x_offset, icon_y_offset);
 }
@@ -5,12 +5,79 @@ use macroquad::{
     window::{screen_height, screen_width},
 };
 
-use crate::draw::draw_common::draw_rounded_rectangle;
+use crate::daily::today;
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_button, format_time};
 use crate::{
     context::Context, ICON_DIFFICULTY_1, ICON_DIFFICULTY_2, ICON_DIFFICULTY_3, ICON_DIFFICULTY_NEW,
     MODAL_BACKGROUND,
 };
 
+fn draw_seed_input(context: &Context) {
+    // show the key of the current game until something is typed
+    let input = &context.new_game_modal.seed_input;
+    let (text, font) = if !input.is_empty() {
+        (
+            format!("Key {}", input.text),
+            &context.font_context.modal_text_font,
+        )
+    } else if let Some(current) = context.board.game_key() {
+        (
+            format!("Key {}", current),
+            &context.font_context.modal_text_font_faded,
+        )
+    } else {
+        (
+            "Seed or key".to_string(),
+            &context.font_context.modal_text_font_faded,
+        )
+    };
+
+    draw_text_button(&context.new_game_modal.seed, &text, font);
+}
+
+// A typed full key overrides the options, they're shown faded with its values.
+fn draw_options(context: &Context) {
+    let modal = &context.new_game_modal;
+    let (symmetry, minimal, font) = match modal.typed_key() {
+        Some(key) => (
+            key.symmetry,
+            key.minimal,
+            &context.font_context.modal_text_font_faded,
+        ),
+        None => (
+            modal.symmetry,
+            modal.minimal,
+            &context.font_context.modal_text_font,
+        ),
+    };
+
+    let text = format!("Symmetry {}", symmetry.name());
+    draw_text_button(&modal.symmetry_button, &text, font);
+
+    let text = if minimal { "Minimal on" } else { "Minimal off" };
+    draw_text_button(&modal.minimal_button, text, font);
+}
+
+fn draw_daily(context: &Context) {
+    let day = today();
+    let streak = context.daily.current_streak(day);
+    let text = match context.daily.result(day) {
+        Some(result) => format!(
+            "Daily done in {} - streak {}",
+            format_time(result.seconds),
+            streak
+        ),
+        None if streak > 0 => format!("Daily - streak {}", streak),
+        None => "Daily".to_string(),
+    };
+
+    draw_text_button(
+        &context.new_game_modal.daily,
+        &text,
+        &context.font_context.modal_text_font,
+    );
+}
+
 pub fn draw_new_game_modal(context: &Context) {
     if !context.new_game_modal.show {
         return;
@@ -29,7 +96,7 @@ pub fn draw_new_game_modal(context: &Context) {
     let title_width = context.new_game_modal.width / 3.0;
     let title_x = context.new_game_modal.x + (context.new_game_modal.width / 2.0);
     let title_y =
-        context.new_game_modal.y - (context.new_game_modal.height / 3.0) + (title_width / 2.0);
+        context.new_game_modal.y - (context.new_game_modal.row_height / 3.0) + (title_width / 2.0);
     draw_poly(title_x, title_y, 80, title_width / 2.0, 0., WHITE);
 
     draw_text_ex(
@@ -40,7 +107,7 @@ pub fn draw_new_game_modal(context: &Context) {
         context.font_context.modal_difficulty_title_font.params,
     );
 
-    let extra_y_offset = (context.new_game_modal.height / 3.0)
+    let extra_y_offset = (context.new_game_modal.row_height / 3.0)
         + (context.font_context.modal_difficulty_font_1.height / 2.0);
 
     draw_text_ex(
@@ -61,4 +128,13 @@ pub fn draw_new_game_modal(context: &Context) {
         context.new_game_modal.difficulty_3.y + extra_y_offset,
         context.font_context.modal_difficulty_font_3.params,
     );
+
+    draw_seed_input(context);
+    draw_options(context);
+    draw_daily(context);
+    draw_text_button(
+        &context.new_game_modal.import,
+        "Import",
+        &context.font_context.modal_text_font,
+    );
 }
@@ -0,0 +1,112 @@
+use macroquad::{
+    prelude::WHITE,
+    shapes::draw_rectangle,
+    text::draw_text_ex,
+    window::{screen_height, screen_width},
+};
+
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_button};
+use crate::key_bindings::KeyAction;
+use crate::{context::Context, MODAL_BACKGROUND};
+
+fn with_shortcut(context: &Context, text: &str, action: KeyAction) -> String {
+    match context.key_bindings.shortcut(action) {
+        Some(key) => format!("{} ({})", text, key),
+        None => text.to_string(),
+    }
+}
+
+fn draw_message(context: &Context) {
+    let modal = &context.options_modal;
+    let (text, font) = if modal.copied {
+        (
+            "Copied to clipboard",
+            &context.font_context.modal_small_text_font,
+        )
+    } else {
+        (
+            "Copy the board to the clipboard",
+            &context.font_context.modal_small_text_font_faded,
+        )
+    };
+
+    draw_text_ex(
+        text,
+        modal.x + (modal.width / 2.0) - (font.measure(text) / 2.0),
+        modal.message_y + (font.height / 2.0),
+        font.params,
+    );
+}
+
+pub fn draw_options_modal(context: &Context) {
+    if !context.options_modal.show {
+        return;
+    }
+
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), MODAL_BACKGROUND);
+    draw_rounded_rectangle(
+        context.options_modal.x,
+        context.options_modal.y,
+        context.options_modal.width,
+        context.options_modal.height,
+        20.0,
+        WHITE,
+    );
+
+    let font = &context.font_context.modal_text_font;
+    draw_text_ex(
+        "Options",
+        context.options_modal.x + (context.options_modal.width / 2.0)
+            - (font.measure("Options") / 2.0),
+        context.options_modal.y + (context.options_modal.title_height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+
+    let free_entry = if context.settings.free_entry {
+        "Free entry on"
+    } else {
+        "Free entry off"
+    };
+    draw_text_button(&context.options_modal.free_entry, free_entry, font);
+
+    let input_mode = if context.settings.cell_first {
+        "Cell first"
+    } else {
+        "Number first"
+    };
+    draw_text_button(&context.options_modal.input_mode, input_mode, font);
+
+    let mistake_limit = match context.settings.mistake_limit {
+        Some(limit) => format!("Mistake limit {}", limit),
+        None => "Mistake limit off".to_string(),
+    };
+    draw_text_button(&context.options_modal.mistake_limit, &mistake_limit, font);
+
+    draw_text_button(
+        &context.options_modal.fill_candidates,
+        "Fill in candidates",
+        font,
+    );
+    let auto_candidates = if context.settings.auto_candidates {
+        "Auto candidates on"
+    } else {
+        "Auto candidates off"
+    };
+    draw_text_button(
+        &context.options_modal.auto_candidates,
+        auto_candidates,
+        font,
+    );
+
+    let checkpoints = with_shortcut(context, "Checkpoints", KeyAction::Checkpoints);
+    draw_text_button(&context.options_modal.checkpoints, &checkpoints, font);
+    let statistics = with_shortcut(context, "Statistics", KeyAction::Statistics);
+    draw_text_button(&context.options_modal.statistics, &statistics, font);
+
+    draw_text_button(&context.options_modal.copy_line, "Copy puzzle", font);
+    draw_text_button(&context.options_modal.copy_grid, "Copy grid", font);
+    let copy_state = with_shortcut(context, "Copy with notes", KeyAction::CopyState);
+    draw_text_button(&context.options_modal.copy_state, &copy_state, font);
+
+    draw_message(context);
+}
@@ -0,0 +1,65 @@
+use macroquad::text::draw_text_ex;
+
+use crate::draw::draw_common::{draw_rounded_rectangle, draw_text_button, format_time};
+use crate::{context::Context, MENU_NUMBER_BACKGROUND_NORMAL, MODAL_INPUT_BACKGROUND};
+
+fn draw_track(context: &Context) {
+    let Some(replay) = &context.replay else {
+        return;
+    };
+
+    let track = &replay.track;
+    draw_rounded_rectangle(
+        track.x,
+        track.y,
+        track.width,
+        track.height,
+        track.height / 2.0,
+        MODAL_INPUT_BACKGROUND,
+    );
+
+    // filled up to the current step
+    if !replay.moves.is_empty() && replay.step > 0 {
+        let filled = track.width * replay.step as f32 / replay.moves.len() as f32;
+        draw_rounded_rectangle(
+            track.x,
+            track.y,
+            filled.max(track.height),
+            track.height,
+            track.height / 2.0,
+            MENU_NUMBER_BACKGROUND_NORMAL,
+        );
+    }
+}
+
+pub fn draw_replay(context: &Context) {
+    let Some(replay) = &context.replay else {
+        return;
+    };
+
+    let font = &context.font_context.menu_text_font;
+    let history = &context.board.history;
+    let time = replay.target().map_or(0.0, |node| history.nodes[node].time);
+    let info = format!(
+        "Move {} of {}  {}",
+        replay.step,
+        replay.moves.len(),
+        format_time(time)
+    );
+    draw_text_ex(
+        &info,
+        replay.x + (replay.width / 2.0) - (font.measure(&info) / 2.0),
+        replay.y + (replay.info_height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+
+    draw_track(context);
+
+    let play = if replay.playing { "Pause" } else { "Play" };
+    draw_text_button(&replay.start, "Start", font);
+    draw_text_button(&replay.back, "Back", font);
+    draw_text_button(&replay.play, play, font);
+    draw_text_button(&replay.next, "Next", font);
+    draw_text_button(&replay.end, "End", font);
+    draw_text_button(&replay.close, "Close", font);
+}
@@ -0,0 +1,75 @@
+use macroquad::{
+    prelude::WHITE,
+    shapes::draw_rectangle,
+    text::draw_text_ex,
+    window::{screen_height, screen_width},
+};
+
+use crate::draw::draw_common::{difficulty_name, draw_rounded_rectangle, format_time};
+use crate::fonts::font_types::TextFont;
+use crate::{context::Context, MODAL_BACKGROUND};
+
+const HEADERS: [&str; 5] = ["Played", "Won", "Best", "Average", "Streak"];
+
+// Centered in its column, the first column being the difficulty names.
+fn draw_cell(text: &str, column: usize, row_y: f32, context: &Context, font: &TextFont) {
+    let modal = &context.statistics_modal;
+    let name_width = modal.width * 0.2;
+    let column_width = (modal.width - name_width) / HEADERS.len() as f32;
+    let x = if column == 0 {
+        modal.x + (modal.width * 0.04)
+    } else {
+        modal.x + name_width + ((column as f32 - 0.5) * column_width) - (font.measure(text) / 2.0)
+    };
+
+    draw_text_ex(
+        text,
+        x,
+        row_y + (modal.row_height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+}
+
+pub fn draw_statistics_modal(context: &Context) {
+    if !context.statistics_modal.show {
+        return;
+    }
+
+    let modal = &context.statistics_modal;
+    draw_rectangle(0.0, 0.0, screen_width(), screen_height(), MODAL_BACKGROUND);
+    draw_rounded_rectangle(modal.x, modal.y, modal.width, modal.height, 20.0, WHITE);
+
+    let font = &context.font_context.modal_text_font;
+    draw_text_ex(
+        "Statistics",
+        modal.x + (modal.width / 2.0) - (font.measure("Statistics") / 2.0),
+        modal.y + (modal.title_height / 2.0) + (font.height / 2.0),
+        font.params,
+    );
+
+    let header_font = &context.font_context.modal_small_text_font_faded;
+    let header_y = modal.y + modal.title_height;
+    for (i, header) in HEADERS.iter().enumerate() {
+        draw_cell(header, i + 1, header_y, context, header_font);
+    }
+
+    let small_font = &context.font_context.modal_small_text_font;
+    for difficulty in 1..=3 {
+        let stats = context.statistics.difficulty_stats(difficulty);
+        let row_y = header_y + (difficulty as f32 * modal.row_height);
+        let values = [
+            difficulty_name(difficulty).to_string(),
+            stats.played.to_string(),
+            stats
+                .win_rate()
+                .map_or("-".to_string(), |rate| format!("{:.0}%", rate)),
+            stats.best_seconds.map_or("-".to_string(), format_time),
+            stats.average_seconds.map_or("-".to_string(), format_time),
+            format!("{}/{}", stats.streak, stats.best_streak),
+        ];
+
+        for (column, value) in values.iter().enumerate() {
+            draw_cell(value, column, row_y, context, small_font);
+        }
+    }
+}
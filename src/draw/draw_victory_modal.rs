@@ -5,43 +5,74 @@ use macroquad::{
     window::{screen_height, screen_width},
 };
 
-use crate::draw::draw_common::draw_rounded_rectangle;
-use crate::{context::Context, ICON_VICTORY_HEART, ICON_VICTORY_STAR, MODAL_BACKGROUND};
+use crate::draw::draw_common::{
+    difficulty_name, draw_rounded_rectangle, draw_text_button, format_time, plural,
+};
+use crate::fonts::font_types::TextFont;
+use crate::{context::Context, MODAL_BACKGROUND};
+
+fn draw_line(context: &Context, line: usize, text: &str, font: &TextFont) {
+    let modal = &context.victory_modal;
+    draw_text_ex(
+        text,
+        modal.x + (modal.width / 2.0) - (font.measure(text) / 2.0),
+        modal.y
+            + modal.title_height
+            + (line as f32 * modal.line_height)
+            + (modal.line_height / 2.0)
+            + (font.height / 2.0),
+        font.params,
+    );
+}
 
 pub fn draw_victory_modal(context: &Context) {
     if !context.victory_modal.show {
         return;
     }
 
+    let modal = &context.victory_modal;
     draw_rectangle(0.0, 0.0, screen_width(), screen_height(), MODAL_BACKGROUND);
-    draw_rounded_rectangle(
-        context.victory_modal.x,
-        context.victory_modal.y,
-        context.victory_modal.width,
-        context.victory_modal.height,
-        20.0,
-        WHITE,
-    );
-
-    let extra_y_offset = (context.victory_modal.height / 3.0)
-        + (context.font_context.modal_victory_star_font.height / 2.0);
+    draw_rounded_rectangle(modal.x, modal.y, modal.width, modal.height, 20.0, WHITE);
 
+    let font = &context.font_context.modal_text_font;
     draw_text_ex(
-        ICON_VICTORY_STAR,
-        context.victory_modal.star_1.x,
-        context.victory_modal.star_1.y + extra_y_offset,
-        context.font_context.modal_victory_star_font.params,
+        "Solved",
+        modal.x + (modal.width / 2.0) - (font.measure("Solved") / 2.0),
+        modal.y + (modal.title_height / 2.0) + (font.height / 2.0),
+        font.params,
     );
-    draw_text_ex(
-        ICON_VICTORY_HEART,
-        context.victory_modal.heart.x,
-        context.victory_modal.heart.y + extra_y_offset,
-        context.font_context.modal_victory_heart_font.params,
-    );
-    draw_text_ex(
-        ICON_VICTORY_STAR,
-        context.victory_modal.star_2.x,
-        context.victory_modal.star_2.y + extra_y_offset,
-        context.font_context.modal_victory_star_font.params,
+
+    let board = &context.board;
+    let small_font = &context.font_context.modal_small_text_font;
+    let faded_font = &context.font_context.modal_small_text_font_faded;
+    let difficulty = difficulty_name(board.difficulty);
+    let time = format!("{} in {}", difficulty, format_time(board.elapsed));
+    draw_line(context, 0, &time, small_font);
+
+    let usage = format!(
+        "{}, {}",
+        plural(board.mistakes, "mistake"),
+        plural(board.hints, "hint")
     );
+    draw_line(context, 1, &usage, faded_font);
+
+    if modal.personal_best {
+        draw_line(context, 2, "New personal best", small_font);
+    } else if let Some(best) = context
+        .statistics
+        .difficulty_stats(board.difficulty)
+        .best_seconds
+    {
+        draw_line(
+            context,
+            2,
+            &format!("Best {}", format_time(best)),
+            faded_font,
+        );
+    }
+
+    let same_difficulty = format!("New {} game", difficulty);
+    draw_text_button(&modal.same_difficulty, &same_difficulty, font);
+    draw_text_button(&modal.choose, "Choose new game", font);
+    draw_text_button(&modal.replay, "Review replay", font);
 }
@@ -1,6 +1,14 @@
 pub mod draw_board;
+pub mod draw_checkpoints_modal;
 pub mod draw_common;
 pub mod draw_context;
+pub mod draw_game_over_modal;
+pub mod draw_hint;
+pub mod draw_import_modal;
+pub mod draw_key_bindings_modal;
 pub mod draw_menu;
 pub mod draw_new_game_modal;
+pub mod draw_options_modal;
+pub mod draw_replay;
+pub mod draw_statistics_modal;
 pub mod draw_victory_modal;
@@ -0,0 +1,159 @@
+use crate::board::Board;
+use crate::cell_state::CellState;
+
+fn given(cell: &CellState) -> Option<u8> {
+    if cell.initial {
+        cell.number
+    } else {
+        None
+    }
+}
+
+// The givens as one line of 81 characters with . for blanks.
+pub fn export_line(board: &Board) -> String {
+    board
+        .cell_state
+        .iter()
+        .map(|cell| given(cell).map_or('.', |number| (b'0' + number) as char))
+        .collect()
+}
+
+// Lays out 81 cell strings as a grid with box separators, every column
+// padded to its widest cell so the rows line up.
+fn format_grid(cells: &[String]) -> String {
+    let mut widths = [0; 9];
+    for (index, cell) in cells.iter().enumerate() {
+        widths[index % 9] = widths[index % 9].max(cell.len());
+    }
+
+    // the dashes cover each box plus the spaces around its | separators
+    let separator = (0..3)
+        .map(|b| {
+            let width: usize = widths[b * 3..b * 3 + 3].iter().sum();
+            let padding = if b == 1 { 4 } else { 3 };
+            "-".repeat(width + padding)
+        })
+        .collect::<Vec<_>>()
+        .join("+");
+
+    let mut lines = Vec::new();
+    for row in 0..9 {
+        if row == 3 || row == 6 {
+            lines.push(separator.clone());
+        }
+
+        let boxes: Vec<String> = (0..3)
+            .map(|b| {
+                (b * 3..b * 3 + 3)
+                    .map(|col| format!("{:<1$}", cells[row * 9 + col], widths[col]))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        lines.push(boxes.join(" | ").trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+// The givens as a readable grid, the import understands it as well.
+pub fn export_grid(board: &Board) -> String {
+    let cells: Vec<String> = board
+        .cell_state
+        .iter()
+        .map(|cell| given(cell).map_or(".".to_string(), |number| number.to_string()))
+        .collect();
+
+    format_grid(&cells)
+}
+
+// The whole position, givens as plain digits, the player's digits with a +
+// in front and empty cells with their pencil marks in brackets or a . without any.
+pub fn export_state(board: &Board) -> String {
+    let cells: Vec<String> = board
+        .cell_state
+        .iter()
+        .map(|cell| match cell.number {
+            Some(number) if cell.initial => number.to_string(),
+            Some(number) => format!("+{}", number),
+            None if cell.has_pencil() => format!(
+                "[{}]",
+                cell.pencil
+                    .iter()
+                    .flatten()
+                    .map(u8::to_string)
+                    .collect::<String>()
+            ),
+            None => ".".to_string(),
+        })
+        .collect();
+
+    format_grid(&cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::parse_puzzle;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    fn board() -> Board {
+        let puzzle = parse_puzzle(PUZZLE).unwrap();
+        let mut board = Board::new();
+        for (index, cell) in board.cell_state.iter_mut().enumerate() {
+            cell.set_initial_number(puzzle[index / 9][index % 9]);
+        }
+        board
+    }
+
+    #[test]
+    fn line() {
+        let mut board = board();
+        assert_eq!(export_line(&board), PUZZLE);
+
+        // only the givens are part of the puzzle
+        board.cell_state[2].set_number(4);
+        assert_eq!(export_line(&board), PUZZLE);
+    }
+
+    #[test]
+    fn grid() {
+        let grid = export_grid(&board());
+        let lines: Vec<&str> = grid.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "5 3 . | . 7 . | . . .");
+        assert_eq!(lines[3], "------+-------+------");
+        assert_eq!(lines[10], ". . . | . 8 . | . 7 9");
+
+        assert_eq!(parse_puzzle(&grid), parse_puzzle(PUZZLE));
+    }
+
+    #[test]
+    fn state() {
+        let mut board = board();
+        board.cell_state[2].set_number(4);
+        board.cell_state[3].set_pencil(2);
+        board.cell_state[3].set_pencil(6);
+
+        let state = export_state(&board);
+        let lines: Vec<&str> = state.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "5 3 +4 | [26] 7 . | . . .");
+        assert_eq!(lines[1], "6 . .  | 1    9 5 | . . .");
+        assert_eq!(lines[3], "-------+----------+------");
+    }
+
+    #[test]
+    fn state_single_pencil() {
+        // a lone pencil mark still can't be taken for a digit
+        let mut board = board();
+        board.cell_state[2].set_pencil(5);
+        board.cell_state[3].set_number(5);
+
+        let state = export_state(&board);
+        let lines: Vec<&str> = state.lines().collect();
+        assert_eq!(lines[0], "5 3 [5] | +5 7 . | . . .");
+    }
+}
@@ -1,28 +1,34 @@
-use macroquad::prelude::{BLACK, WHITE};
+use macroquad::prelude::{BLACK, GRAY, WHITE};
 
 use crate::{
-    CELL_TEXT_COLOR, CELL_TEXT_INITIAL_COLOR, MODAL_DIFFICULTY_ONE, MODAL_DIFFICULTY_THREE,
-    MODAL_DIFFICULTY_TWO, MODAL_VICTORY_GOLD, MODAL_VICTORY_RED,
+    CELL_TEXT_COLOR, CELL_TEXT_INITIAL_COLOR, CELL_TEXT_WRONG_COLOR, MODAL_DIFFICULTY_ONE,
+    MODAL_DIFFICULTY_THREE, MODAL_DIFFICULTY_TWO, MODAL_ERROR_TEXT,
 };
 
 use super::font_types::{
-    CellFont, CellPencilFont, IconFont, MenuNumberFont, ModalDifficultyFont, ModalVictoryFont,
+    CellFont, CellPencilFont, IconFont, MenuNumberFont, ModalDifficultyFont, TextFont,
 };
 
 pub struct FontContext {
     pub initial_font: CellFont,
     pub font: CellFont,
+    pub wrong_font: CellFont,
     pub icon_font: IconFont,
     pub icon_font_selected: IconFont,
     pub pencil_font: CellPencilFont,
     pub menu_number_font: MenuNumberFont,
     pub menu_number_font_selected: MenuNumberFont,
+    pub menu_text_font: TextFont,
+    pub menu_text_font_faded: TextFont,
     pub modal_difficulty_font_1: ModalDifficultyFont,
     pub modal_difficulty_font_2: ModalDifficultyFont,
     pub modal_difficulty_font_3: ModalDifficultyFont,
     pub modal_difficulty_title_font: ModalDifficultyFont,
-    pub modal_victory_star_font: ModalVictoryFont,
-    pub modal_victory_heart_font: ModalVictoryFont,
+    pub modal_text_font: TextFont,
+    pub modal_text_font_faded: TextFont,
+    pub modal_small_text_font: TextFont,
+    pub modal_small_text_font_faded: TextFont,
+    pub modal_error_font: TextFont,
 }
 
 impl FontContext {
@@ -30,11 +36,14 @@ impl FontContext {
         FontContext {
             initial_font: CellFont::new(font_path, CELL_TEXT_INITIAL_COLOR).await,
             font: CellFont::new(font_path, CELL_TEXT_COLOR).await,
+            wrong_font: CellFont::new(font_path, CELL_TEXT_WRONG_COLOR).await,
             icon_font: IconFont::new(icon_font_path, BLACK).await,
             icon_font_selected: IconFont::new(icon_font_path, WHITE).await,
             pencil_font: CellPencilFont::new(font_path).await,
             menu_number_font: MenuNumberFont::new(font_path, BLACK).await,
             menu_number_font_selected: MenuNumberFont::new(font_path, WHITE).await,
+            menu_text_font: TextFont::new(0.3, BLACK),
+            menu_text_font_faded: TextFont::new(0.3, GRAY),
             modal_difficulty_font_1: ModalDifficultyFont::new(
                 icon_font_path,
                 0.5,
@@ -54,26 +63,33 @@ impl FontContext {
             )
             .await,
             modal_difficulty_title_font: ModalDifficultyFont::new(icon_font_path, 0.7, BLACK).await,
-            modal_victory_star_font: ModalVictoryFont::new(icon_font_path, 0.5, MODAL_VICTORY_GOLD)
-                .await,
-            modal_victory_heart_font: ModalVictoryFont::new(icon_font_path, 0.5, MODAL_VICTORY_RED)
-                .await,
+            modal_text_font: TextFont::new(0.5, BLACK),
+            modal_text_font_faded: TextFont::new(0.5, GRAY),
+            modal_small_text_font: TextFont::new(0.35, BLACK),
+            modal_small_text_font_faded: TextFont::new(0.35, GRAY),
+            modal_error_font: TextFont::new(0.35, MODAL_ERROR_TEXT),
         }
     }
 
     pub fn update(&mut self, cell_size: f32) {
         self.initial_font.update(cell_size);
         self.font.update(cell_size);
+        self.wrong_font.update(cell_size);
         self.pencil_font.update(cell_size);
         self.menu_number_font.update(cell_size);
         self.menu_number_font_selected.update(cell_size);
+        self.menu_text_font.update(cell_size);
+        self.menu_text_font_faded.update(cell_size);
         self.icon_font.update(cell_size);
         self.icon_font_selected.update(cell_size);
         self.modal_difficulty_font_1.update(cell_size);
         self.modal_difficulty_font_2.update(cell_size);
         self.modal_difficulty_font_3.update(cell_size);
-        self.modal_victory_heart_font.update(cell_size);
-        self.modal_victory_star_font.update(cell_size);
         self.modal_difficulty_title_font.update(cell_size);
+        self.modal_text_font.update(cell_size);
+        self.modal_text_font_faded.update(cell_size);
+        self.modal_small_text_font.update(cell_size);
+        self.modal_small_text_font_faded.update(cell_size);
+        self.modal_error_font.update(cell_size);
     }
 }
@@ -1,7 +1,7 @@
 use macroquad::prelude::*;
 use macroquad::text::TextParams;
 
-use crate::{ICON_DIFFICULTY_1, ICON_PENCIL, ICON_VICTORY_STAR};
+use crate::{ICON_DIFFICULTY_1, ICON_PENCIL};
 
 use super::font_size::estimate_font_size;
 
@@ -207,22 +207,20 @@ impl ModalDifficultyFont {
     }
 }
 
-pub struct ModalVictoryFont {
+// Uses macroquad's built in font, the bundled fonts only contain digits and icons.
+pub struct TextFont {
     pub params: TextParams,
-    pub font: Font,
     pub height: f32,
     pub width: f32,
     pub estimate_scale: f32,
 }
 
-impl ModalVictoryFont {
-    pub async fn new(font_path: &str, estimate_scale: f32, color: Color) -> Self {
-        let font = load_ttf_font(font_path).await.unwrap();
-        let measure = measure_text(ICON_VICTORY_STAR, Some(font), 48, 1.0);
-        ModalVictoryFont {
-            font,
+impl TextFont {
+    pub fn new(estimate_scale: f32, color: Color) -> Self {
+        let measure = measure_text("W", None, 48, 1.0);
+        TextFont {
             params: TextParams {
-                font,
+                font: Font::default(),
                 font_size: 48,
                 font_scale: 1.0,
                 font_scale_aspect: 1.0,
@@ -236,19 +234,13 @@ impl ModalVictoryFont {
     }
 
     pub fn update(&mut self, cell_size: f32) {
-        self.params.font_size = estimate_font_size(
-            ICON_VICTORY_STAR,
-            Some(self.font),
-            cell_size,
-            self.estimate_scale,
-        );
-        let measure = measure_text(
-            ICON_VICTORY_STAR,
-            Some(self.font),
-            self.params.font_size,
-            1.0,
-        );
+        self.params.font_size = estimate_font_size("W", None, cell_size * self.estimate_scale, 1.0);
+        let measure = measure_text("W", None, self.params.font_size, 1.0);
         self.width = measure.width;
         self.height = measure.height;
     }
+
+    pub fn measure(&self, text: &str) -> f32 {
+        measure_text(text, None, self.params.font_size, 1.0).width
+    }
 }
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::symmetry::Symmetry;
+
+// Everything a generated puzzle comes from, so a reported key gives the same
+// puzzle back. Written as the seed followed by the difficulty, the symmetry and
+// 1 for a minimal puzzle, e.g. 1234-210. Only digits, so the letters stay free
+// for the new game screen's shortcuts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameKey {
+    pub seed: u64,
+    pub difficulty: u8,
+    pub symmetry: Symmetry,
+    pub minimal: bool,
+}
+
+impl GameKey {
+    // Only the full form, a bare seed is up to the caller.
+    pub fn parse(text: &str) -> Option<GameKey> {
+        let (seed, options) = text.trim().split_once('-')?;
+        let seed = seed.parse().ok()?;
+
+        let &[difficulty, symmetry, minimal] = options.as_bytes() else {
+            return None;
+        };
+        let digit = |byte: u8| (byte as char).to_digit(10).map(|digit| digit as u8);
+
+        let difficulty = digit(difficulty).filter(|difficulty| (1..=3).contains(difficulty))?;
+        let symmetry = Symmetry::from_number(digit(symmetry)?)?;
+        let minimal = match digit(minimal)? {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+
+        Some(GameKey {
+            seed,
+            difficulty,
+            symmetry,
+            minimal,
+        })
+    }
+}
+
+impl fmt::Display for GameKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}{}{}",
+            self.seed,
+            self.difficulty,
+            self.symmetry.number(),
+            self.minimal as u8
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let key = GameKey {
+            seed: 1234,
+            difficulty: 2,
+            symmetry: Symmetry::Rotational,
+            minimal: false,
+        };
+        assert_eq!(key.to_string(), "1234-210");
+        assert_eq!(GameKey::parse("1234-210"), Some(key));
+
+        for symmetry in Symmetry::ALL {
+            let key = GameKey {
+                seed: 987654321,
+                difficulty: 3,
+                symmetry,
+                minimal: true,
+            };
+            assert_eq!(GameKey::parse(&key.to_string()), Some(key));
+        }
+    }
+
+    #[test]
+    fn parse_rejects() {
+        assert_eq!(GameKey::parse("1234"), None);
+        assert_eq!(GameKey::parse("-210"), None);
+        assert_eq!(GameKey::parse("1234-21"), None);
+        assert_eq!(GameKey::parse("1234-2100"), None);
+        assert_eq!(GameKey::parse("1234-410"), None);
+        assert_eq!(GameKey::parse("1234-250"), None);
+        assert_eq!(GameKey::parse("1234-212"), None);
+        assert_eq!(GameKey::parse("12-34-210"), None);
+    }
+}
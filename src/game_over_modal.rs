@@ -0,0 +1,76 @@
+use crate::text_button::TextButton;
+
+pub struct GameOverModal {
+    pub show: bool,
+    pub x: f32,
+    pub y: f32,
+    pub game_square: f32,
+    pub width: f32,
+    pub height: f32,
+    pub title_height: f32,
+    pub retry: TextButton,
+    pub new_game: TextButton,
+}
+
+impl Default for GameOverModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameOverModal {
+    pub fn new() -> Self {
+        GameOverModal {
+            show: false,
+            x: 0.0,
+            y: 0.0,
+            game_square: 0.0,
+            width: 0.0,
+            height: 0.0,
+            title_height: 0.0,
+            retry: Default::default(),
+            new_game: Default::default(),
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.show = true;
+
+        self.update_game_over();
+    }
+
+    pub fn hide(&mut self) {
+        self.show = false;
+    }
+
+    pub fn update(&mut self, game_square: f32) {
+        self.game_square = game_square;
+
+        if self.show {
+            self.update_game_over();
+        }
+    }
+
+    pub fn update_game_over(&mut self) {
+        let cell_width = self.game_square / 9.0;
+        self.width = cell_width * 4.0;
+        self.x = self.game_square / 2.0 - (self.width / 2.0);
+        self.title_height = cell_width * 1.25;
+        self.height = self.title_height + (cell_width * 0.75);
+        self.y = self.game_square / 2.0 - (self.height / 2.0);
+
+        let button_width = (self.width - (cell_width * 0.75)) / 2.0;
+        self.retry.update(
+            self.x + (cell_width * 0.25),
+            self.y + self.title_height,
+            button_width,
+            cell_width * 0.5,
+        );
+        self.new_game.update(
+            self.x + (cell_width * 0.5) + button_width,
+            self.y + self.title_height,
+            button_width,
+            cell_width * 0.5,
+        );
+    }
+}
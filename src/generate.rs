@@ -1,30 +1,43 @@
-use macroquad::rand::gen_range;
+use crate::grade::grade_puzzle;
+use crate::index::index_to_xy;
+use crate::rng::Rng;
+use crate::solver::{fill, is_unique};
+use crate::symmetry::Symmetry;
 
 const DIGITS: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
 
-fn shuffle<T>(array: &mut [T]) {
-    let mut i = array.len();
-    while i >= 2 {
-        i -= 1;
-        let j = gen_range(0, i);
-        array.swap(i, j);
-    }
+// Puzzles tried on one solved grid before starting over with a fresh one
+const ATTEMPTS_PER_GRID: usize = 10;
+
+pub struct Puzzle {
+    pub givens: [[u8; 9]; 9],
+    pub solution: [[u8; 9]; 9],
+}
+
+// Same seed and options always give the same puzzle.
+pub fn generate_puzzle(seed: u64, difficulty: u8, symmetry: Symmetry, minimal: bool) -> Puzzle {
+    let mut rng = Rng::new(seed);
+    let mut givens = [[0; 9]; 9];
+    generate_board(&mut givens, &mut rng);
+
+    let solution = create_puzzle(&mut givens, difficulty, symmetry, minimal, &mut rng);
+    Puzzle { givens, solution }
 }
 
-pub fn generate_board(board: &mut [[u8; 9]; 9]) {
-    fill_diagonal(board);
-    solve(board);
+pub fn generate_board(board: &mut [[u8; 9]; 9], rng: &mut Rng) {
+    fill_diagonal(board, rng);
+    fill(board, rng);
 }
 
-fn fill_diagonal(board: &mut [[u8; 9]; 9]) {
+fn fill_diagonal(board: &mut [[u8; 9]; 9], rng: &mut Rng) {
     for i in (0..9).step_by(3) {
-        fill_subgrid(board, i, i);
+        fill_subgrid(board, i, i, rng);
     }
 }
 
-fn fill_subgrid(board: &mut [[u8; 9]; 9], row: usize, col: usize) {
+fn fill_subgrid(board: &mut [[u8; 9]; 9], row: usize, col: usize, rng: &mut Rng) {
     let mut values = DIGITS;
-    shuffle(&mut values);
+    rng.shuffle(&mut values);
 
     for i in 0..3 {
         for j in 0..3 {
@@ -33,183 +46,248 @@ fn fill_subgrid(board: &mut [[u8; 9]; 9], row: usize, col: usize) {
     }
 }
 
-fn solve(board: &mut [[u8; 9]; 9]) -> bool {
-    if let Some((row, col)) = find_empty_cell(board) {
-        let mut values = DIGITS;
-        shuffle(&mut values);
+// 2nd part of the puzzle generation algorithm
 
-        for value in values {
-            if is_valid(board, row, col, value) {
-                board[row][col] = value;
+// A minimal puzzle keeps removing single clues after the symmetric pass until
+// every remaining clue is needed for a unique solution, which can break the symmetry.
+// The board comes in solved and is left with the clues, the solution is returned
+// since it changes when the first grid doesn't give a puzzle of the right tier.
+pub fn create_puzzle(
+    board: &mut [[u8; 9]; 9],
+    difficulty: u8,
+    symmetry: Symmetry,
+    minimal: bool,
+    rng: &mut Rng,
+) -> [[u8; 9]; 9] {
+    // Easy puzzles keep some extra clues, the others remove as many as they can
+    let max_cells_to_remove = match difficulty {
+        1 => 45, // Easy
+        2 => 81, // Medium
+        3 => 81, // Hard
+        _ => panic!("Invalid difficulty level!"),
+    };
 
-                if solve(board) {
-                    return true;
-                }
+    // Keep producing puzzles until one needs the techniques of the requested tier,
+    // so the puzzle is always what the player asked for
+    let mut solution = *board;
+    loop {
+        for _ in 0..ATTEMPTS_PER_GRID {
+            *board = solution;
+            remove_cells(board, symmetry, max_cells_to_remove, rng);
+            if minimal {
+                minimize(board, rng);
+            }
 
-                board[row][col] = 0; // Backtrack
+            if grade_puzzle(board).difficulty == difficulty {
+                return solution;
             }
         }
 
-        return false;
+        solution = [[0; 9]; 9];
+        generate_board(&mut solution, rng);
     }
-
-    true
 }
 
-fn find_empty_cell(board: &[[u8; 9]; 9]) -> Option<(usize, usize)> {
-    for (row, row_values) in board.iter().enumerate() {
-        for (col, &value) in row_values.iter().enumerate() {
-            if value == 0 {
-                return Some((row, col));
-            }
-        }
-    }
-    None
-}
+fn remove_cells(
+    board: &mut [[u8; 9]; 9],
+    symmetry: Symmetry,
+    max_cells_to_remove: usize,
+    rng: &mut Rng,
+) {
+    let mut cells: Vec<usize> = (0..81).collect();
+    rng.shuffle(&mut cells);
 
-fn is_valid(board: &[[u8; 9]; 9], row: usize, col: usize, value: u8) -> bool {
-    // Check row and column
-    for i in 0..9 {
-        if board[row][i] == value || board[i][col] == value {
-            return false;
+    let mut cells_removed = 0;
+    for cell in cells {
+        // Cells are removed together with their symmetric partner
+        let group: Vec<(usize, usize)> = symmetry
+            .group(cell)
+            .into_iter()
+            .map(|index| index_to_xy(index, 9))
+            .filter(|(col, row)| board[*row][*col] != 0)
+            .collect();
+        if group.is_empty() || cells_removed + group.len() > max_cells_to_remove {
+            continue;
         }
-    }
 
-    // Check subgrid
-    let subgrid_row = 3 * (row / 3);
-    let subgrid_col = 3 * (col / 3);
-    for i in 0..3 {
-        for j in 0..3 {
-            if board[subgrid_row + i][subgrid_col + j] == value {
-                return false;
-            }
+        // Backup the cell values and temporarily remove them
+        let backup = *board;
+        for (col, row) in group.iter() {
+            board[*row][*col] = 0;
         }
-    }
-
-    true
-}
-
-// 2nd part of the puzzle generation algorithm
-
-pub fn create_puzzle(board: &mut [[u8; 9]; 9], difficulty: u8) {
-    // Determine the number of cells to remove based on difficulty
-    let num_cells_to_remove = match difficulty {
-        1 => 35, // Easy
-        2 => 45, // Medium
-        3 => 52, // Hard
-        _ => panic!("Invalid difficulty level!"),
-    };
 
-    // Remove cells randomly until the desired number is reached
-    let mut cells_removed = 0;
-    while cells_removed < num_cells_to_remove {
-        let row = gen_range(0, 9);
-        let col = gen_range(0, 9);
-
-        if board[row][col] != 0 {
-            // Backup the cell value and temporarily remove it
-            let backup = board[row][col];
-            board[row][col] = 0;
-
-            // Check if the puzzle is still solvable with the current configuration
-            let mut temp_board = *board;
-            if has_unique_solution(&mut temp_board) {
-                cells_removed += 1;
-            } else {
-                // If the puzzle is not unique, restore the backup value
-                board[row][col] = backup;
-            }
+        // The puzzle has to stay unique, its tier is checked once it's done
+        if is_unique(board) {
+            cells_removed += group.len();
+        } else {
+            *board = backup;
         }
     }
 }
 
-fn has_unique_solution(board: &mut [[u8; 9]; 9]) -> bool {
-    let mut solutions = 0;
-    solve_with_unique_solution(board, &mut solutions);
-    solutions == 1
-}
-
-fn solve_with_unique_solution(board: &mut [[u8; 9]; 9], solution_count: &mut usize) {
-    if let Some((row, col)) = find_empty_cell(board) {
-        let mut values = DIGITS;
-        shuffle(&mut values);
-
-        for value in values {
-            if is_valid(board, row, col, value) {
-                board[row][col] = value;
-
-                if *solution_count < 2 {
-                    solve_with_unique_solution(board, solution_count);
-                    if *solution_count > 1 {
-                        return;
-                    }
-                }
-
-                board[row][col] = 0; // Backtrack
-            }
+// Removing clues never brings back uniqueness, so a clue that is needed once
+// stays needed and a single pass over all clues leaves a minimal puzzle.
+fn minimize(board: &mut [[u8; 9]; 9], rng: &mut Rng) {
+    let mut cells: Vec<usize> = (0..81).collect();
+    rng.shuffle(&mut cells);
+
+    for cell in cells {
+        let (col, row) = index_to_xy(cell, 9);
+        let value = board[row][col];
+        if value == 0 {
+            continue;
         }
 
-        return;
+        board[row][col] = 0;
+        if !is_unique(board) {
+            board[row][col] = value;
+        }
     }
-
-    *solution_count += 1;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solver::{count_solutions, solve};
 
     #[test]
     fn test_create_easy() {
+        let mut rng = Rng::new(1);
         let mut board = [[0; 9]; 9];
-        generate_board(&mut board);
+        generate_board(&mut board, &mut rng);
 
-        create_puzzle(&mut board, 1); // Easy difficulty
+        create_puzzle(&mut board, 1, Symmetry::None, false, &mut rng); // Easy difficulty
 
-        // Count the number of filled cells
-        let num_filled_cells = board.iter().flatten().filter(|&&value| value != 0).count();
+        // Check that the puzzle needs the techniques of the expected difficulty level
+        assert_eq!(grade_puzzle(&board).difficulty, 1);
 
-        // Check that the number of filled cells matches the expected difficulty level
-        assert_eq!(num_filled_cells, 81 - 35); // 81 is the total number of cells in the board
+        // Easy puzzles keep at least 36 clues
+        let num_filled_cells = board.iter().flatten().filter(|&&value| value != 0).count();
+        assert!(num_filled_cells >= 81 - 45);
 
         // Check that the puzzle is still solvable and has a unique solution
-        let mut puzzle_board = board;
-        assert!(has_unique_solution(&mut puzzle_board));
+        assert!(is_unique(&board));
     }
 
     #[test]
     fn test_create_medium() {
+        let mut rng = Rng::new(1);
         let mut board = [[0; 9]; 9];
-        generate_board(&mut board);
+        generate_board(&mut board, &mut rng);
 
-        create_puzzle(&mut board, 2); // Medium difficulty
-
-        // Count the number of filled cells
-        let num_filled_cells = board.iter().flatten().filter(|&&value| value != 0).count();
+        create_puzzle(&mut board, 2, Symmetry::None, false, &mut rng); // Medium difficulty
 
-        // Check that the number of filled cells matches the expected difficulty level
-        assert_eq!(num_filled_cells, 81 - 45); // 81 is the total number of cells in the board
+        // Check that the puzzle needs the techniques of the expected difficulty level
+        assert_eq!(grade_puzzle(&board).difficulty, 2);
 
         // Check that the puzzle is still solvable and has a unique solution
-        let mut puzzle_board = board;
-        assert!(has_unique_solution(&mut puzzle_board));
+        assert!(is_unique(&board));
     }
 
     #[test]
     fn test_create_hard() {
+        let mut rng = Rng::new(1);
         let mut board = [[0; 9]; 9];
-        generate_board(&mut board);
+        generate_board(&mut board, &mut rng);
 
-        create_puzzle(&mut board, 3); // Hard difficulty
+        create_puzzle(&mut board, 3, Symmetry::None, false, &mut rng); // Hard difficulty
 
-        // Count the number of filled cells
-        let num_filled_cells = board.iter().flatten().filter(|&&value| value != 0).count();
-
-        // Check that the number of filled cells matches the expected difficulty level
-        assert_eq!(num_filled_cells, 81 - 52); // 81 is the total number of cells in the board
+        // Check that the puzzle needs the techniques of the expected difficulty level
+        assert_eq!(grade_puzzle(&board).difficulty, 3);
 
         // Check that the puzzle is still solvable and has a unique solution
-        let mut puzzle_board = board;
-        assert!(has_unique_solution(&mut puzzle_board));
+        assert!(is_unique(&board));
+    }
+
+    #[test]
+    fn test_solution() {
+        let puzzle = generate_puzzle(42, 2, Symmetry::Rotational, false);
+
+        let mut solved = puzzle.givens;
+        assert!(solve(&mut solved));
+        assert_eq!(solved, puzzle.solution);
+    }
+
+    #[test]
+    fn test_same_seed() {
+        let puzzle = generate_puzzle(1234, 2, Symmetry::None, false).givens;
+        assert_eq!(
+            puzzle,
+            generate_puzzle(1234, 2, Symmetry::None, false).givens
+        );
+        assert_ne!(
+            puzzle,
+            generate_puzzle(1235, 2, Symmetry::None, false).givens
+        );
+        assert!(is_unique(&puzzle));
+    }
+
+    #[test]
+    fn test_symmetric() {
+        let symmetries = [
+            Symmetry::Rotational,
+            Symmetry::MirrorHorizontal,
+            Symmetry::MirrorVertical,
+            Symmetry::Diagonal,
+        ];
+
+        for (i, symmetry) in symmetries.into_iter().enumerate() {
+            let puzzle = generate_puzzle(i as u64, 2, symmetry, false).givens;
+            assert!(is_unique(&puzzle));
+            assert_eq!(grade_puzzle(&puzzle).difficulty, 2);
+
+            for index in 0..81 {
+                let (col, row) = index_to_xy(index, 9);
+                let (partner_col, partner_row) = index_to_xy(symmetry.partner(index), 9);
+                assert_eq!(puzzle[row][col] == 0, puzzle[partner_row][partner_col] == 0);
+            }
+        }
+    }
+
+    // Every single clue of a minimal puzzle is needed, removing any of them
+    // has to leave more than one solution.
+    fn assert_minimal(puzzle: &[[u8; 9]; 9]) {
+        assert!(is_unique(puzzle));
+
+        for index in 0..81 {
+            let (col, row) = index_to_xy(index, 9);
+            if puzzle[row][col] == 0 {
+                continue;
+            }
+
+            let mut reduced = *puzzle;
+            reduced[row][col] = 0;
+            assert!(!is_unique(&reduced));
+            assert_eq!(count_solutions(&reduced, 2), 2);
+        }
+    }
+
+    #[test]
+    fn test_minimal() {
+        for (seed, difficulty) in [(1, 3), (2, 3), (3, 2)] {
+            let puzzle = generate_puzzle(seed, difficulty, Symmetry::None, true).givens;
+            assert_eq!(grade_puzzle(&puzzle).difficulty, difficulty);
+            assert_minimal(&puzzle);
+        }
+    }
+
+    #[test]
+    fn test_minimal_symmetric() {
+        let puzzle = generate_puzzle(4, 3, Symmetry::Rotational, true).givens;
+        assert_eq!(grade_puzzle(&puzzle).difficulty, 3);
+        assert_minimal(&puzzle);
+    }
+
+    #[test]
+    fn test_not_minimal() {
+        // easy puzzles keep spare clues, so at least one of them can go
+        let puzzle = generate_puzzle(5, 1, Symmetry::None, false).givens;
+        let removable = (0..81).any(|index| {
+            let (col, row) = index_to_xy(index, 9);
+            let mut reduced = puzzle;
+            reduced[row][col] = 0;
+            puzzle[row][col] != 0 && is_unique(&reduced)
+        });
+        assert!(removable);
     }
 }
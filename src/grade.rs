@@ -0,0 +1,152 @@
+use crate::logic::candidate_grid::CandidateGrid;
+use crate::logic::logic_solver::solve_logically;
+use crate::logic::solve_step::Technique;
+
+// Anything the logical solver can't finish is at least this expensive.
+const UNSOLVED_SCORE: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grade {
+    // 1 easy, 2 medium, 3 hard. Hard also covers puzzles that need more than
+    // the techniques here, which can mean having to guess somewhere.
+    pub difficulty: u8,
+    pub score: u32,
+    pub hardest: Option<Technique>,
+    pub solved: bool,
+}
+
+pub fn technique_difficulty(technique: Technique) -> u8 {
+    // techniques are ordered from simplest to hardest
+    if technique <= Technique::HiddenSingle {
+        1
+    } else if technique <= Technique::BoxLineReduction {
+        2
+    } else {
+        3
+    }
+}
+
+pub fn technique_score(technique: Technique) -> u32 {
+    // scores grow in the same order as the techniques
+    match technique {
+        Technique::NakedSingle => 1,
+        Technique::HiddenSingle => 2,
+        Technique::NakedPair => 10,
+        Technique::HiddenPair => 15,
+        Technique::NakedTriple => 20,
+        Technique::HiddenTriple => 25,
+        Technique::PointingPair => 30,
+        Technique::BoxLineReduction => 35,
+        Technique::XWing => 50,
+        Technique::Swordfish => 60,
+        Technique::XYWing => 70,
+        Technique::SimpleColoring => 80,
+    }
+}
+
+// The tier comes from the hardest technique the puzzle needs, the score adds up
+// every step so a puzzle leaning on a technique over and over scores higher.
+// Puzzles the logical solver can't finish are graded as hard.
+pub fn grade_puzzle(board: &[[u8; 9]; 9]) -> Grade {
+    let solution = solve_logically(&CandidateGrid::from_board(board));
+
+    let hardest = solution.steps.iter().map(|step| step.technique).max();
+    let mut score = solution
+        .steps
+        .iter()
+        .map(|step| technique_score(step.technique))
+        .sum();
+
+    let difficulty = if solution.solved {
+        hardest.map_or(1, technique_difficulty)
+    } else {
+        score += UNSOLVED_SCORE;
+        3
+    };
+
+    Grade {
+        difficulty,
+        score,
+        hardest,
+        solved: solution.solved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grade_singles() {
+        let board = [
+            [5, 3, 0, 0, 7, 0, 0, 0, 0],
+            [6, 0, 0, 1, 9, 5, 0, 0, 0],
+            [0, 9, 8, 0, 0, 0, 0, 6, 0],
+            [8, 0, 0, 0, 6, 0, 0, 0, 3],
+            [4, 0, 0, 8, 0, 3, 0, 0, 1],
+            [7, 0, 0, 0, 2, 0, 0, 0, 6],
+            [0, 6, 0, 0, 0, 0, 2, 8, 0],
+            [0, 0, 0, 4, 1, 9, 0, 0, 5],
+            [0, 0, 0, 0, 8, 0, 0, 7, 9],
+        ];
+
+        let grade = grade_puzzle(&board);
+        assert!(grade.solved);
+        assert_eq!(grade.difficulty, 1);
+        assert!(grade.score >= 51);
+        assert!(grade.hardest <= Some(Technique::HiddenSingle));
+    }
+
+    #[test]
+    fn grade_solved() {
+        let mut board = [[0; 9]; 9];
+        crate::solver::solve(&mut board);
+
+        let grade = grade_puzzle(&board);
+        assert!(grade.solved);
+        assert_eq!(grade.difficulty, 1);
+        assert_eq!(grade.score, 0);
+        assert_eq!(grade.hardest, None);
+    }
+
+    #[test]
+    fn grade_unsolved() {
+        // an empty grid has no logical steps at all
+        let grade = grade_puzzle(&[[0; 9]; 9]);
+        assert!(!grade.solved);
+        assert_eq!(grade.difficulty, 3);
+        assert!(grade.score >= UNSOLVED_SCORE);
+    }
+
+    #[test]
+    fn technique_tiers() {
+        assert_eq!(technique_difficulty(Technique::HiddenSingle), 1);
+        assert_eq!(technique_difficulty(Technique::PointingPair), 2);
+        assert_eq!(technique_difficulty(Technique::Swordfish), 3);
+        assert!(technique_score(Technique::NakedSingle) < technique_score(Technique::XWing));
+    }
+
+    #[test]
+    fn technique_scores_follow_order() {
+        let techniques = [
+            Technique::NakedSingle,
+            Technique::HiddenSingle,
+            Technique::NakedPair,
+            Technique::HiddenPair,
+            Technique::NakedTriple,
+            Technique::HiddenTriple,
+            Technique::PointingPair,
+            Technique::BoxLineReduction,
+            Technique::XWing,
+            Technique::Swordfish,
+            Technique::XYWing,
+            Technique::SimpleColoring,
+        ];
+
+        for pair in techniques.windows(2) {
+            assert!(pair[0] < pair[1]);
+            assert!(technique_score(pair[0]) < technique_score(pair[1]));
+            assert!(technique_difficulty(pair[0]) <= technique_difficulty(pair[1]));
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::cell_state::CellState;
+use crate::index::index_to_xy;
+use crate::logic::candidate_grid::CandidateGrid;
+use crate::logic::house::cell_name;
+use crate::logic::logic_solver::{apply_step, find_step};
+use crate::logic::solve_step::SolveStep;
+use crate::solver::count_solutions;
+
+pub const MAX_HINT_LEVEL: u8 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HintKind {
+    Step(SolveStep),
+    // The numbers on the board can't lead to a solution anymore.
+    Mistake,
+    // None of the techniques applies, or the board is already full.
+    Stuck,
+}
+
+// Every level gives away a bit more, first where to look, then the
+// technique and at last the whole move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    pub kind: HintKind,
+    pub level: u8,
+}
+
+// Notes only count as done if the cell has any, an empty cell without
+// notes just hasn't been looked at yet.
+fn is_noted(cell_state: &[CellState; 81], step: &SolveStep) -> bool {
+    step.placement.is_none()
+        && step.eliminations.iter().all(|(index, digit)| {
+            let cell = &cell_state[*index];
+            cell.has_pencil() && !cell.has_this_pencil(*digit)
+        })
+}
+
+impl Hint {
+    pub fn find(cell_state: &[CellState; 81]) -> Self {
+        let mut board = [[0; 9]; 9];
+        for (index, cell) in cell_state.iter().enumerate() {
+            let (x, y) = index_to_xy(index, 9);
+            board[y][x] = cell.number.unwrap_or(0);
+        }
+
+        let kind = if count_solutions(&board, 1) == 0 {
+            HintKind::Mistake
+        } else {
+            // skip the eliminations the player already made in their notes
+            let mut grid = CandidateGrid::from_board(&board);
+            loop {
+                match find_step(&grid) {
+                    Some(step) if is_noted(cell_state, &step) => apply_step(&mut grid, &step),
+                    Some(step) => break HintKind::Step(step),
+                    None => break HintKind::Stuck,
+                }
+            }
+        };
+
+        Hint { kind, level: 1 }
+    }
+
+    pub fn next_level(&mut self) {
+        self.level = (self.level + 1).min(MAX_HINT_LEVEL);
+    }
+
+    pub fn message(&self) -> String {
+        let step = match &self.kind {
+            HintKind::Step(step) => step,
+            HintKind::Mistake => return "Some numbers on the board are wrong".to_string(),
+            HintKind::Stuck => return "No logical step found".to_string(),
+        };
+
+        let name = step.technique.name();
+        let house = step.houses.first();
+        match (self.level, house) {
+            (1, Some(house)) => format!("Look at {}", house.name()),
+            (1, None) => "Look at the highlighted cells".to_string(),
+            (2, Some(house)) => format!("{} in {}", name, house.name()),
+            (2, None) => name.to_string(),
+            _ => format!("{}: {}", name, describe_move(step)),
+        }
+    }
+
+    // The cells to mark as part of the hint, and the ones around them
+    // that are only highlighted.
+    pub fn highlights(&self) -> (Vec<usize>, Vec<usize>) {
+        let HintKind::Step(step) = &self.kind else {
+            return (vec![], vec![]);
+        };
+
+        let house_cells: Vec<usize> = step
+            .houses
+            .iter()
+            .flat_map(|house| house.indexes().iter().copied())
+            .collect();
+
+        if self.level == 1 {
+            if house_cells.is_empty() {
+                return (step.cells.clone(), vec![]);
+            }
+            return (house_cells, vec![]);
+        }
+
+        let mut cells = step.cells.clone();
+        if self.level >= MAX_HINT_LEVEL {
+            cells.extend(step.placement.iter().map(|(index, _)| *index));
+            cells.extend(step.eliminations.iter().map(|(index, _)| *index));
+        }
+
+        (cells, house_cells)
+    }
+}
+
+fn describe_move(step: &SolveStep) -> String {
+    if let Some((index, digit)) = step.placement {
+        return format!("{} is {}", cell_name(index), digit);
+    }
+
+    let mut digits: Vec<u8> = step.eliminations.iter().map(|(_, digit)| *digit).collect();
+    digits.sort();
+    digits.dedup();
+
+    let removals: Vec<String> = digits
+        .into_iter()
+        .map(|digit| {
+            let cells: Vec<String> = step
+                .eliminations
+                .iter()
+                .filter(|(_, eliminated)| *eliminated == digit)
+                .map(|(index, _)| cell_name(*index))
+                .collect();
+            format!("{} from {}", digit, cells.join(", "))
+        })
+        .collect();
+
+    format!("remove {}", removals.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::house::House;
+    use crate::logic::solve_step::Technique;
+
+    const PUZZLE: &str =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+
+    fn cells(puzzle: &str) -> [CellState; 81] {
+        let mut cell_state = [CellState::new(); 81];
+        for (cell, character) in cell_state.iter_mut().zip(puzzle.chars()) {
+            cell.set_initial_number(character as u8 - b'0');
+        }
+        cell_state
+    }
+
+    #[test]
+    fn levels() {
+        let mut hint = Hint::find(&cells(PUZZLE));
+        let HintKind::Step(step) = hint.kind.clone() else {
+            panic!("expected a step");
+        };
+        assert_eq!(step.technique, Technique::NakedSingle);
+        assert!(step.placement.is_some());
+
+        assert_eq!(hint.level, 1);
+        assert_eq!(hint.message(), "Look at the highlighted cells");
+        assert_eq!(hint.highlights(), (step.cells.clone(), vec![]));
+
+        hint.next_level();
+        assert_eq!(hint.message(), "Naked single");
+
+        hint.next_level();
+        hint.next_level();
+        assert_eq!(hint.level, MAX_HINT_LEVEL);
+        let (index, digit) = step.placement.unwrap();
+        assert_eq!(
+            hint.message(),
+            format!("Naked single: {} is {}", cell_name(index), digit)
+        );
+    }
+
+    #[test]
+    fn mistake() {
+        // the solution has a 4 in r1c3
+        let mut cell_state = cells(PUZZLE);
+        cell_state[2].set_number(1);
+
+        let hint = Hint::find(&cell_state);
+        assert_eq!(hint.kind, HintKind::Mistake);
+        assert_eq!(hint.highlights(), (vec![], vec![]));
+    }
+
+    #[test]
+    fn solved() {
+        let solved =
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+        assert_eq!(Hint::find(&cells(solved)).kind, HintKind::Stuck);
+    }
+
+    #[test]
+    fn skips_noted_eliminations() {
+        let step = SolveStep::elimination(
+            Technique::PointingPair,
+            vec![House::Box(0)],
+            vec![0, 1],
+            vec![4],
+            vec![(5, 4), (6, 4)],
+        )
+        .unwrap();
+
+        let mut cell_state = [CellState::new(); 81];
+        assert!(!is_noted(&cell_state, &step));
+
+        cell_state[5].set_pencil(3);
+        assert!(!is_noted(&cell_state, &step));
+
+        cell_state[6].set_pencil(3);
+        assert!(is_noted(&cell_state, &step));
+
+        cell_state[6].set_pencil(4);
+        assert!(!is_noted(&cell_state, &step));
+    }
+
+    #[test]
+    fn elimination_message() {
+        let hint = Hint {
+            kind: HintKind::Step(
+                SolveStep::elimination(
+                    Technique::PointingPair,
+                    vec![House::Box(0)],
+                    vec![0, 1],
+                    vec![4],
+                    vec![(5, 4), (6, 4), (7, 5)],
+                )
+                .unwrap(),
+            ),
+            level: 2,
+        };
+        assert_eq!(hint.message(), "Pointing pair in box 1");
+
+        let (cells, highlighted) = hint.highlights();
+        assert_eq!(cells, vec![0, 1]);
+        assert_eq!(highlighted.len(), 9);
+
+        let hint = Hint { level: 3, ..hint };
+        assert_eq!(
+            hint.message(),
+            "Pointing pair: remove 4 from r1c6, r1c7, 5 from r1c8"
+        );
+        assert_eq!(hint.highlights().0, vec![0, 1, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+
+use crate::index::index_to_xy;
+use crate::logic::house::{cell_name, sees};
+use crate::solver::count_solutions;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportError {
+    InvalidCharacter(char),
+    WrongLength(usize),
+    // Two givens with the same digit in one row, column or box.
+    Conflict(usize, usize),
+    NoSolution,
+    MultipleSolutions,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::InvalidCharacter(character) => {
+                write!(f, "Invalid character '{}'", character)
+            }
+            ImportError::WrongLength(length) => write!(f, "Expected 81 cells, got {}", length),
+            ImportError::Conflict(a, b) => write!(
+                f,
+                "Conflict between {} and {}",
+                cell_name(*a),
+                cell_name(*b)
+            ),
+            ImportError::NoSolution => write!(f, "Puzzle has no solution"),
+            ImportError::MultipleSolutions => write!(f, "Puzzle has more than one solution"),
+        }
+    }
+}
+
+// Characters drawn between boxes in pretty printed grids.
+fn is_separator(character: char) -> bool {
+    character.is_whitespace() || matches!(character, '|' | '-' | '+' | '=')
+}
+
+// Reads the common one line format, digits with 0 or . for blanks. Whitespace
+// and grid separators are skipped so printed grids can be pasted too.
+pub fn parse_puzzle(text: &str) -> Result<[[u8; 9]; 9], ImportError> {
+    let mut values = Vec::with_capacity(81);
+    for character in text.chars() {
+        match character {
+            '0' | '.' => values.push(0),
+            '1'..='9' => values.push(character as u8 - b'0'),
+            _ if is_separator(character) => {}
+            _ => return Err(ImportError::InvalidCharacter(character)),
+        }
+    }
+
+    if values.len() != 81 {
+        return Err(ImportError::WrongLength(values.len()));
+    }
+
+    let mut board = [[0; 9]; 9];
+    for (index, value) in values.into_iter().enumerate() {
+        let (col, row) = index_to_xy(index, 9);
+        board[row][col] = value;
+    }
+
+    Ok(board)
+}
+
+// Parses the puzzle and makes sure it can be played, the givens can't
+// contradict each other and there has to be exactly one solution.
+pub fn import_puzzle(text: &str) -> Result<[[u8; 9]; 9], ImportError> {
+    let board = parse_puzzle(text)?;
+
+    let value = |index: usize| {
+        let (col, row) = index_to_xy(index, 9);
+        board[row][col]
+    };
+    for a in 0..81 {
+        for b in (a + 1)..81 {
+            if value(a) != 0 && value(a) == value(b) && sees(a, b) {
+                return Err(ImportError::Conflict(a, b));
+            }
+        }
+    }
+
+    match count_solutions(&board, 2) {
+        0 => Err(ImportError::NoSolution),
+        1 => Ok(board),
+        _ => Err(ImportError::MultipleSolutions),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+
+    #[test]
+    fn parse_line() {
+        let board = parse_puzzle(PUZZLE).unwrap();
+        assert_eq!(board[0], [5, 3, 0, 0, 7, 0, 0, 0, 0]);
+        assert_eq!(board[8], [0, 0, 0, 0, 8, 0, 0, 7, 9]);
+
+        let dotted = PUZZLE.replace('0', ".");
+        assert_eq!(parse_puzzle(&dotted), Ok(board));
+    }
+
+    #[test]
+    fn parse_grid() {
+        let grid = "
+            5 3 . | . 7 . | . . .
+            6 . . | 1 9 5 | . . .
+            . 9 8 | . . . | . 6 .
+            ------+-------+------
+            8 . . | . 6 . | . . 3
+            4 . . | 8 . 3 | . . 1
+            7 . . | . 2 . | . . 6
+            ------+-------+------
+            . 6 . | . . . | 2 8 .
+            . . . | 4 1 9 | . . 5
+            . . . | . 8 . | . 7 9
+        ";
+        assert_eq!(parse_puzzle(grid), parse_puzzle(PUZZLE));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_puzzle(""), Err(ImportError::WrongLength(0)));
+        assert_eq!(
+            parse_puzzle(&PUZZLE[1..]),
+            Err(ImportError::WrongLength(80))
+        );
+        assert_eq!(
+            parse_puzzle(&format!("{}1", PUZZLE)),
+            Err(ImportError::WrongLength(82))
+        );
+        assert_eq!(
+            parse_puzzle(&PUZZLE.replacen('0', "x", 1)),
+            Err(ImportError::InvalidCharacter('x'))
+        );
+    }
+
+    #[test]
+    fn import_valid() {
+        assert_eq!(import_puzzle(PUZZLE), parse_puzzle(PUZZLE));
+    }
+
+    #[test]
+    fn import_conflict() {
+        // a second 5 in the first row
+        let puzzle = format!("5305{}", &PUZZLE[4..]);
+        assert_eq!(import_puzzle(&puzzle), Err(ImportError::Conflict(0, 3)));
+    }
+
+    #[test]
+    fn import_no_solution() {
+        // consistent givens, but r1c9 can only be a 9 and r2c9 already is
+        let puzzle = format!("123456780000000009{}", "0".repeat(63));
+        assert_eq!(import_puzzle(&puzzle), Err(ImportError::NoSolution));
+    }
+
+    #[test]
+    fn import_multiple_solutions() {
+        assert_eq!(
+            import_puzzle(&"0".repeat(81)),
+            Err(ImportError::MultipleSolutions)
+        );
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            ImportError::Conflict(0, 3).to_string(),
+            "Conflict between r1c1 and r1c4"
+        );
+        assert_eq!(
+            ImportError::WrongLength(80).to_string(),
+            "Expected 81 cells, got 80"
+        );
+    }
+}
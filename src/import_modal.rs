@@ -0,0 +1,102 @@
+use crate::import::ImportError;
+use crate::text_button::TextButton;
+use crate::text_input::TextInput;
+
+pub struct ImportModal {
+    pub show: bool,
+    pub x: f32,
+    pub y: f32,
+    pub game_square: f32,
+    pub width: f32,
+    pub height: f32,
+    pub title_height: f32,
+    pub grid_x: f32,
+    pub grid_y: f32,
+    pub grid_size: f32,
+    pub message_y: f32,
+    pub input: TextInput,
+    pub error: Option<ImportError>,
+    pub paste: TextButton,
+    pub start: TextButton,
+}
+
+impl Default for ImportModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportModal {
+    pub fn new() -> Self {
+        ImportModal {
+            show: false,
+            x: 0.0,
+            y: 0.0,
+            game_square: 0.0,
+            width: 0.0,
+            height: 0.0,
+            title_height: 0.0,
+            grid_x: 0.0,
+            grid_y: 0.0,
+            grid_size: 0.0,
+            message_y: 0.0,
+            input: TextInput::new(81, |c| c.is_ascii_digit() || c == '.'),
+            error: None,
+            paste: Default::default(),
+            start: Default::default(),
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.show = true;
+        self.input.clear();
+        self.error = None;
+
+        self.update_import();
+    }
+
+    pub fn hide(&mut self) {
+        self.show = false;
+    }
+
+    pub fn update(&mut self, game_square: f32) {
+        self.game_square = game_square;
+
+        if self.show {
+            self.update_import();
+        }
+    }
+
+    pub fn update_import(&mut self) {
+        let cell_width = self.game_square / 9.0;
+        self.width = cell_width * 5.0;
+        self.x = self.game_square / 2.0 - (self.width / 2.0);
+        self.title_height = cell_width * 0.75;
+        self.grid_size = cell_width * 4.0;
+        self.height = self.title_height + self.grid_size + (cell_width * 1.5);
+        self.y = self.game_square / 2.0 - (self.height / 2.0);
+
+        self.grid_x = self.x + (cell_width * 0.5);
+        self.grid_y = self.y + self.title_height;
+        self.message_y = self.grid_y + self.grid_size + (cell_width * 0.35);
+
+        let button_y = self.grid_y + self.grid_size + (cell_width * 0.75);
+        let button_width = (self.width - (cell_width * 0.75)) / 2.0;
+        self.paste.update(
+            self.x + (cell_width * 0.25),
+            button_y,
+            button_width,
+            cell_width * 0.5,
+        );
+        self.start.update(
+            self.x + (cell_width * 0.5) + button_width,
+            button_y,
+            button_width,
+            cell_width * 0.5,
+        );
+    }
+
+    pub fn click_outside(&mut self, x: f32, y: f32) -> bool {
+        x < self.x || x > self.x + self.width || y < self.y || y > self.y + self.height
+    }
+}
@@ -0,0 +1,735 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use macroquad::input::{is_key_down, KeyCode};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::save::{load, save};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Clear,
+    PencilMode,
+    Undo,
+    Redo,
+    Check,
+    Reveal,
+    Hint,
+    CopyState,
+    AddCheckpoint,
+    Checkpoints,
+    Statistics,
+    Pause,
+    Options,
+    NewGame,
+    Easy,
+    Medium,
+    Hard,
+    Daily,
+    Retry,
+    NextGame,
+    ReplayPlay,
+    ReplayBack,
+    ReplayNext,
+    ReplayStart,
+    ReplayEnd,
+    ReplayClose,
+}
+
+// Where an action's keys are read, a key can only be bound once per screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyScope {
+    Game,
+    NewGame,
+    // The victory and game over screens.
+    GameEnd,
+    Replay,
+}
+
+impl KeyAction {
+    // In the order they are listed for rebinding.
+    pub const ALL: [KeyAction; 39] = [
+        KeyAction::Up,
+        KeyAction::Down,
+        KeyAction::Left,
+        KeyAction::Right,
+        KeyAction::One,
+        KeyAction::Two,
+        KeyAction::Three,
+        KeyAction::Four,
+        KeyAction::Five,
+        KeyAction::Six,
+        KeyAction::Seven,
+        KeyAction::Eight,
+        KeyAction::Nine,
+        KeyAction::Clear,
+        KeyAction::PencilMode,
+        KeyAction::Undo,
+        KeyAction::Redo,
+        KeyAction::Check,
+        KeyAction::Reveal,
+        KeyAction::Hint,
+        KeyAction::CopyState,
+        KeyAction::AddCheckpoint,
+        KeyAction::Checkpoints,
+        KeyAction::Statistics,
+        KeyAction::Pause,
+        KeyAction::Options,
+        KeyAction::NewGame,
+        KeyAction::Easy,
+        KeyAction::Medium,
+        KeyAction::Hard,
+        KeyAction::Daily,
+        KeyAction::Retry,
+        KeyAction::NextGame,
+        KeyAction::ReplayPlay,
+        KeyAction::ReplayBack,
+        KeyAction::ReplayNext,
+        KeyAction::ReplayStart,
+        KeyAction::ReplayEnd,
+        KeyAction::ReplayClose,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyAction::Up => "Move up",
+            KeyAction::Down => "Move down",
+            KeyAction::Left => "Move left",
+            KeyAction::Right => "Move right",
+            KeyAction::One => "Number 1",
+            KeyAction::Two => "Number 2",
+            KeyAction::Three => "Number 3",
+            KeyAction::Four => "Number 4",
+            KeyAction::Five => "Number 5",
+            KeyAction::Six => "Number 6",
+            KeyAction::Seven => "Number 7",
+            KeyAction::Eight => "Number 8",
+            KeyAction::Nine => "Number 9",
+            KeyAction::Clear => "Clear cell",
+            KeyAction::PencilMode => "Pencil mode",
+            KeyAction::Undo => "Undo",
+            KeyAction::Redo => "Redo",
+            KeyAction::Check => "Check",
+            KeyAction::Reveal => "Reveal",
+            KeyAction::Hint => "Hint",
+            KeyAction::CopyState => "Copy with notes",
+            KeyAction::AddCheckpoint => "Add checkpoint",
+            KeyAction::Checkpoints => "Checkpoints",
+            KeyAction::Statistics => "Statistics",
+            KeyAction::Pause => "Pause",
+            KeyAction::Options => "Options",
+            KeyAction::NewGame => "New game",
+            KeyAction::Easy => "Start easy",
+            KeyAction::Medium => "Start medium",
+            KeyAction::Hard => "Start hard",
+            KeyAction::Daily => "Start daily",
+            KeyAction::Retry => "Retry lost game",
+            KeyAction::NextGame => "Pick next game",
+            KeyAction::ReplayPlay => "Play replay",
+            KeyAction::ReplayBack => "Replay back",
+            KeyAction::ReplayNext => "Replay forward",
+            KeyAction::ReplayStart => "Replay start",
+            KeyAction::ReplayEnd => "Replay end",
+            KeyAction::ReplayClose => "Close replay",
+        }
+    }
+
+    pub fn scope(self) -> KeyScope {
+        match self {
+            KeyAction::Easy | KeyAction::Medium | KeyAction::Hard | KeyAction::Daily => {
+                KeyScope::NewGame
+            }
+            KeyAction::Retry | KeyAction::NextGame => KeyScope::GameEnd,
+            KeyAction::ReplayPlay
+            | KeyAction::ReplayBack
+            | KeyAction::ReplayNext
+            | KeyAction::ReplayStart
+            | KeyAction::ReplayEnd
+            | KeyAction::ReplayClose => KeyScope::Replay,
+            _ => KeyScope::Game,
+        }
+    }
+
+    // The number the action puts in, for the nine number actions.
+    pub fn number(self) -> Option<u8> {
+        KeyAction::ALL[4..13]
+            .iter()
+            .position(|action| *action == self)
+            .map(|i| i as u8 + 1)
+    }
+
+    fn default_keys(self) -> Vec<KeyCombo> {
+        let keys = match self {
+            KeyAction::Up => vec![KeyCode::Up, KeyCode::W, KeyCode::K],
+            KeyAction::Down => vec![KeyCode::Down, KeyCode::S, KeyCode::J],
+            KeyAction::Left => vec![KeyCode::Left, KeyCode::A, KeyCode::H],
+            KeyAction::Right => vec![KeyCode::Right, KeyCode::D, KeyCode::L],
+            KeyAction::One => vec![KeyCode::Key1, KeyCode::Kp1],
+            KeyAction::Two => vec![KeyCode::Key2, KeyCode::Kp2],
+            KeyAction::Three => vec![KeyCode::Key3, KeyCode::Kp3],
+            KeyAction::Four => vec![KeyCode::Key4, KeyCode::Kp4],
+            KeyAction::Five => vec![KeyCode::Key5, KeyCode::Kp5],
+            KeyAction::Six => vec![KeyCode::Key6, KeyCode::Kp6],
+            KeyAction::Seven => vec![KeyCode::Key7, KeyCode::Kp7],
+            KeyAction::Eight => vec![KeyCode::Key8, KeyCode::Kp8],
+            KeyAction::Nine => vec![KeyCode::Key9, KeyCode::Kp9],
+            KeyAction::Clear => vec![
+                KeyCode::Key0,
+                KeyCode::Kp0,
+                KeyCode::Delete,
+                KeyCode::Backspace,
+            ],
+            KeyAction::PencilMode => vec![KeyCode::Space],
+            KeyAction::Undo => vec![KeyCode::U],
+            // along with the usual ctrl+y and ctrl+shift+z
+            KeyAction::Redo => {
+                return vec![
+                    KeyCombo::new(KeyCode::R),
+                    KeyCombo {
+                        control: true,
+                        ..KeyCombo::new(KeyCode::Y)
+                    },
+                    KeyCombo {
+                        control: true,
+                        shift: true,
+                        key: KeyCode::Z,
+                    },
+                ]
+            }
+            KeyAction::Check => vec![KeyCode::E],
+            KeyAction::Reveal => vec![KeyCode::F],
+            KeyAction::Hint => vec![KeyCode::I],
+            KeyAction::CopyState => vec![KeyCode::C],
+            KeyAction::AddCheckpoint => vec![KeyCode::B],
+            KeyAction::Checkpoints => vec![KeyCode::G],
+            KeyAction::Statistics => vec![KeyCode::T],
+            KeyAction::Pause => vec![KeyCode::P],
+            KeyAction::Options => vec![KeyCode::O],
+            KeyAction::NewGame => vec![KeyCode::N],
+            KeyAction::Easy => vec![KeyCode::E],
+            KeyAction::Medium => vec![KeyCode::M],
+            KeyAction::Hard => vec![KeyCode::H],
+            KeyAction::Daily => vec![KeyCode::D],
+            KeyAction::Retry => vec![KeyCode::R],
+            KeyAction::NextGame => vec![KeyCode::N],
+            KeyAction::ReplayPlay => vec![KeyCode::Space],
+            KeyAction::ReplayBack => vec![KeyCode::Left],
+            KeyAction::ReplayNext => vec![KeyCode::Right],
+            KeyAction::ReplayStart => vec![KeyCode::Home],
+            KeyAction::ReplayEnd => vec![KeyCode::End],
+            KeyAction::ReplayClose => vec![KeyCode::Backspace],
+        };
+        keys.into_iter().map(KeyCombo::new).collect()
+    }
+}
+
+// The keys that can be bound, with the names they are shown and saved as.
+// Anything else, like shift or escape, is left alone.
+const KEY_NAMES: [(KeyCode, &str); 76] = [
+    (KeyCode::A, "A"),
+    (KeyCode::B, "B"),
+    (KeyCode::C, "C"),
+    (KeyCode::D, "D"),
+    (KeyCode::E, "E"),
+    (KeyCode::F, "F"),
+    (KeyCode::G, "G"),
+    (KeyCode::H, "H"),
+    (KeyCode::I, "I"),
+    (KeyCode::J, "J"),
+    (KeyCode::K, "K"),
+    (KeyCode::L, "L"),
+    (KeyCode::M, "M"),
+    (KeyCode::N, "N"),
+    (KeyCode::O, "O"),
+    (KeyCode::P, "P"),
+    (KeyCode::Q, "Q"),
+    (KeyCode::R, "R"),
+    (KeyCode::S, "S"),
+    (KeyCode::T, "T"),
+    (KeyCode::U, "U"),
+    (KeyCode::V, "V"),
+    (KeyCode::W, "W"),
+    (KeyCode::X, "X"),
+    (KeyCode::Y, "Y"),
+    (KeyCode::Z, "Z"),
+    (KeyCode::Key0, "0"),
+    (KeyCode::Key1, "1"),
+    (KeyCode::Key2, "2"),
+    (KeyCode::Key3, "3"),
+    (KeyCode::Key4, "4"),
+    (KeyCode::Key5, "5"),
+    (KeyCode::Key6, "6"),
+    (KeyCode::Key7, "7"),
+    (KeyCode::Key8, "8"),
+    (KeyCode::Key9, "9"),
+    (KeyCode::Kp0, "Num 0"),
+    (KeyCode::Kp1, "Num 1"),
+    (KeyCode::Kp2, "Num 2"),
+    (KeyCode::Kp3, "Num 3"),
+    (KeyCode::Kp4, "Num 4"),
+    (KeyCode::Kp5, "Num 5"),
+    (KeyCode::Kp6, "Num 6"),
+    (KeyCode::Kp7, "Num 7"),
+    (KeyCode::Kp8, "Num 8"),
+    (KeyCode::Kp9, "Num 9"),
+    (KeyCode::KpDecimal, "Num ."),
+    (KeyCode::KpDivide, "Num /"),
+    (KeyCode::KpMultiply, "Num *"),
+    (KeyCode::KpSubtract, "Num -"),
+    (KeyCode::KpAdd, "Num +"),
+    (KeyCode::KpEnter, "Num Enter"),
+    (KeyCode::Up, "Up"),
+    (KeyCode::Down, "Down"),
+    (KeyCode::Left, "Left"),
+    (KeyCode::Right, "Right"),
+    (KeyCode::Space, "Space"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::Insert, "Insert"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageUp, "Page Up"),
+    (KeyCode::PageDown, "Page Down"),
+    (KeyCode::Minus, "-"),
+    (KeyCode::Equal, "="),
+    (KeyCode::LeftBracket, "["),
+    (KeyCode::RightBracket, "]"),
+    (KeyCode::Semicolon, ";"),
+    (KeyCode::Apostrophe, "'"),
+    (KeyCode::Comma, ","),
+    (KeyCode::Period, "."),
+    (KeyCode::Slash, "/"),
+    (KeyCode::Backslash, "\\"),
+];
+
+pub fn key_name(key: KeyCode) -> Option<&'static str> {
+    KEY_NAMES
+        .iter()
+        .find(|(code, _)| *code == key)
+        .map(|(_, name)| *name)
+}
+
+fn key_from_name(name: &str) -> Option<KeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(_, key_name)| *key_name == name)
+        .map(|(code, _)| *code)
+}
+
+fn is_modifier(key: KeyCode) -> bool {
+    matches!(
+        key,
+        KeyCode::LeftControl
+            | KeyCode::RightControl
+            | KeyCode::LeftShift
+            | KeyCode::RightShift
+            | KeyCode::LeftAlt
+            | KeyCode::RightAlt
+            | KeyCode::LeftSuper
+            | KeyCode::RightSuper
+    )
+}
+
+// A key with the modifiers held down with it, saved as e.g. "Ctrl+Shift+Z".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub key: KeyCode,
+    pub control: bool,
+    pub shift: bool,
+}
+
+impl KeyCombo {
+    pub fn new(key: KeyCode) -> Self {
+        KeyCombo {
+            key,
+            control: false,
+            shift: false,
+        }
+    }
+
+    // The key as it was pressed just now, None for a modifier on its own.
+    pub fn pressed(key: KeyCode) -> Option<Self> {
+        if is_modifier(key) {
+            return None;
+        }
+
+        Some(KeyCombo {
+            key,
+            control: is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl),
+            shift: is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift),
+        })
+    }
+
+    pub fn name(&self) -> Option<String> {
+        let mut name = String::new();
+        if self.control {
+            name.push_str("Ctrl+");
+        }
+        if self.shift {
+            name.push_str("Shift+");
+        }
+        name.push_str(key_name(self.key)?);
+        Some(name)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let (control, name) = match name.strip_prefix("Ctrl+") {
+            Some(rest) => (true, rest),
+            None => (false, name),
+        };
+        let (shift, name) = match name.strip_prefix("Shift+") {
+            Some(rest) => (true, rest),
+            None => (false, name),
+        };
+
+        key_from_name(name).map(|key| KeyCombo {
+            key,
+            control,
+            shift,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindError {
+    // Not a key that can be bound.
+    Unsupported(KeyCode),
+    InUse(KeyCombo, KeyAction),
+}
+
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindError::Unsupported(key) => write!(f, "{:?} can't be used", key),
+            BindError::InUse(combo, action) => write!(
+                f,
+                "{} is used for {}",
+                combo.name().unwrap_or("The key".to_string()),
+                action.name()
+            ),
+        }
+    }
+}
+
+// Saved as the names of the keys for each action, actions that are missing
+// keep their default keys.
+#[derive(Debug, PartialEq)]
+pub struct KeyBindings {
+    keys: HashMap<KeyAction, Vec<KeyCombo>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            keys: KeyAction::ALL
+                .iter()
+                .map(|action| (*action, action.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn load() -> Self {
+        load("key_bindings")
+            .and_then(|bindings_str| serde_json::from_str(&bindings_str).ok())
+            .map(|saved| KeyBindings::from_saved(&saved))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let save_data = serde_json::to_string(&self.to_saved()).unwrap();
+        save("key_bindings", save_data.as_str());
+    }
+
+    fn from_saved(saved: &HashMap<KeyAction, Vec<String>>) -> Self {
+        let mut bindings = KeyBindings::default();
+        for (action, names) in saved {
+            let keys = names
+                .iter()
+                .filter_map(|name| KeyCombo::from_name(name))
+                .collect();
+            bindings.keys.insert(*action, keys);
+        }
+        bindings
+    }
+
+    fn to_saved(&self) -> HashMap<KeyAction, Vec<String>> {
+        self.keys
+            .iter()
+            .map(|(action, keys)| {
+                let names = keys.iter().filter_map(KeyCombo::name).collect();
+                (*action, names)
+            })
+            .collect()
+    }
+
+    pub fn keys(&self, action: KeyAction) -> &[KeyCombo] {
+        self.keys.get(&action).map_or(&[], |keys| keys.as_slice())
+    }
+
+    // The first key of the action, to show next to its button.
+    pub fn shortcut(&self, action: KeyAction) -> Option<String> {
+        self.keys(action).first().and_then(KeyCombo::name)
+    }
+
+    fn bound(&self, scope: KeyScope, combo: KeyCombo) -> Option<KeyAction> {
+        KeyAction::ALL
+            .iter()
+            .copied()
+            .filter(|action| action.scope() == scope)
+            .find(|action| self.keys(*action).contains(&combo))
+    }
+
+    // A key held with modifiers nothing is bound to counts as the key on its
+    // own, so shift can still change what the action does.
+    pub fn action(&self, scope: KeyScope, combo: KeyCombo) -> Option<KeyAction> {
+        self.bound(scope, combo)
+            .or_else(|| self.bound(scope, KeyCombo::new(combo.key)))
+    }
+
+    // Looked up the same way as a pressed key, so a combo can't take over
+    // from the action its key on its own is bound to either.
+    fn check(&self, action: KeyAction, combo: KeyCombo) -> Result<(), BindError> {
+        if key_name(combo.key).is_none() {
+            return Err(BindError::Unsupported(combo.key));
+        }
+
+        match self.action(action.scope(), combo) {
+            Some(other) if other != action => Err(BindError::InUse(combo, other)),
+            _ => Ok(()),
+        }
+    }
+
+    // Replaces the action's keys with the one key, as long as nothing else
+    // on the same screen uses it.
+    pub fn bind(&mut self, action: KeyAction, combo: KeyCombo) -> Result<(), BindError> {
+        self.check(action, combo)?;
+        self.keys.insert(action, vec![combo]);
+        Ok(())
+    }
+
+    // Adds the key next to the ones the action already has.
+    pub fn add(&mut self, action: KeyAction, combo: KeyCombo) -> Result<(), BindError> {
+        self.check(action, combo)?;
+        let keys = self.keys.entry(action).or_default();
+        if !keys.contains(&combo) {
+            keys.push(combo);
+        }
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        *self = KeyBindings::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use macroquad::input::KeyCode;
+
+    use super::{
+        key_from_name, key_name, BindError, KeyAction, KeyBindings, KeyCombo, KeyScope, KEY_NAMES,
+    };
+
+    fn control(key: KeyCode) -> KeyCombo {
+        KeyCombo {
+            control: true,
+            ..KeyCombo::new(key)
+        }
+    }
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        let bindings = KeyBindings::default();
+        for action in KeyAction::ALL {
+            assert!(!bindings.keys(action).is_empty());
+            for combo in bindings.keys(action) {
+                assert_eq!(bindings.action(action.scope(), *combo), Some(action));
+                assert!(combo.name().is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(KeyAction::One.number(), Some(1));
+        assert_eq!(KeyAction::Nine.number(), Some(9));
+        assert_eq!(KeyAction::Clear.number(), None);
+    }
+
+    #[test]
+    fn test_bind() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(
+            bindings.bind(KeyAction::Hint, KeyCombo::new(KeyCode::U)),
+            Err(BindError::InUse(KeyCombo::new(KeyCode::U), KeyAction::Undo))
+        );
+        assert_eq!(
+            bindings.bind(KeyAction::Hint, KeyCombo::new(KeyCode::Escape)),
+            Err(BindError::Unsupported(KeyCode::Escape))
+        );
+        let hint = KeyCombo::new(KeyCode::I);
+        assert_eq!(bindings.action(KeyScope::Game, hint), Some(KeyAction::Hint));
+
+        // azerty players move with zqsd
+        let up = KeyCombo::new(KeyCode::Z);
+        assert_eq!(bindings.bind(KeyAction::Up, up), Ok(()));
+        assert_eq!(bindings.keys(KeyAction::Up), &[up]);
+        let w = KeyCombo::new(KeyCode::W);
+        assert_eq!(bindings.action(KeyScope::Game, w), None);
+
+        // binding the key an action already has is fine
+        assert_eq!(bindings.bind(KeyAction::Up, up), Ok(()));
+
+        bindings.reset();
+        assert_eq!(bindings, KeyBindings::default());
+    }
+
+    #[test]
+    fn test_add() {
+        let mut bindings = KeyBindings::default();
+        let q = KeyCombo::new(KeyCode::Q);
+        assert_eq!(bindings.add(KeyAction::Left, q), Ok(()));
+        assert_eq!(bindings.add(KeyAction::Left, q), Ok(()));
+        assert_eq!(bindings.keys(KeyAction::Left).len(), 4);
+        assert_eq!(bindings.keys(KeyAction::Left)[3], q);
+        assert_eq!(bindings.action(KeyScope::Game, q), Some(KeyAction::Left));
+
+        // the default keys are still there
+        let a = KeyCombo::new(KeyCode::A);
+        assert_eq!(bindings.action(KeyScope::Game, a), Some(KeyAction::Left));
+        assert_eq!(
+            bindings.add(KeyAction::Right, q),
+            Err(BindError::InUse(q, KeyAction::Left))
+        );
+    }
+
+    #[test]
+    fn test_scopes() {
+        let mut bindings = KeyBindings::default();
+
+        // the same key does something else on each screen
+        let e = KeyCombo::new(KeyCode::E);
+        assert_eq!(bindings.action(KeyScope::Game, e), Some(KeyAction::Check));
+        assert_eq!(bindings.action(KeyScope::NewGame, e), Some(KeyAction::Easy));
+        assert_eq!(bindings.action(KeyScope::GameEnd, e), None);
+        assert_eq!(bindings.action(KeyScope::Replay, e), None);
+        let space = KeyCombo::new(KeyCode::Space);
+        assert_eq!(
+            bindings.action(KeyScope::Game, space),
+            Some(KeyAction::PencilMode)
+        );
+        assert_eq!(
+            bindings.action(KeyScope::Replay, space),
+            Some(KeyAction::ReplayPlay)
+        );
+        assert_eq!(bindings.bind(KeyAction::Retry, e), Ok(()));
+
+        let n = KeyCombo::new(KeyCode::N);
+        assert_eq!(
+            bindings.bind(KeyAction::Daily, KeyCombo::new(KeyCode::M)),
+            Err(BindError::InUse(
+                KeyCombo::new(KeyCode::M),
+                KeyAction::Medium
+            ))
+        );
+        assert_eq!(bindings.bind(KeyAction::Daily, n), Ok(()));
+        assert_eq!(
+            bindings.action(KeyScope::NewGame, n),
+            Some(KeyAction::Daily)
+        );
+        assert_eq!(bindings.action(KeyScope::Game, n), Some(KeyAction::NewGame));
+    }
+
+    #[test]
+    fn test_combos() {
+        let mut bindings = KeyBindings::default();
+        let redo = KeyCombo {
+            shift: true,
+            ..control(KeyCode::Z)
+        };
+        assert_eq!(bindings.action(KeyScope::Game, redo), Some(KeyAction::Redo));
+        assert_eq!(
+            bindings.action(KeyScope::Game, control(KeyCode::Y)),
+            Some(KeyAction::Redo)
+        );
+
+        // shift on a number is still the number, for pencil marks
+        let pencil = KeyCombo {
+            shift: true,
+            ..KeyCombo::new(KeyCode::Key4)
+        };
+        assert_eq!(
+            bindings.action(KeyScope::Game, pencil),
+            Some(KeyAction::Four)
+        );
+
+        // ctrl+y can be moved somewhere else
+        assert_eq!(bindings.bind(KeyAction::Redo, control(KeyCode::R)), Ok(()));
+        assert_eq!(bindings.action(KeyScope::Game, control(KeyCode::Y)), None);
+        assert_eq!(
+            bindings.action(KeyScope::Game, control(KeyCode::R)),
+            Some(KeyAction::Redo)
+        );
+        assert_eq!(bindings.bind(KeyAction::Undo, control(KeyCode::Y)), Ok(()));
+
+        // but shift on a number can't be taken away from pencil marks
+        assert_eq!(
+            bindings.bind(KeyAction::Hint, pencil),
+            Err(BindError::InUse(pencil, KeyAction::Four))
+        );
+
+        assert_eq!(redo.name(), Some("Ctrl+Shift+Z".to_string()));
+        assert_eq!(KeyCombo::from_name("Ctrl+Shift+Z"), Some(redo));
+        assert_eq!(KeyCombo::from_name("Shift+Ctrl+Z"), None);
+    }
+
+    #[test]
+    fn test_saved() {
+        let mut bindings = KeyBindings::default();
+        bindings
+            .bind(KeyAction::Left, KeyCombo::new(KeyCode::Q))
+            .unwrap();
+        bindings
+            .bind(KeyAction::Hint, KeyCombo::new(KeyCode::Slash))
+            .unwrap();
+        bindings.add(KeyAction::Undo, control(KeyCode::Z)).unwrap();
+
+        let json = serde_json::to_string(&bindings.to_saved()).unwrap();
+        let saved: HashMap<KeyAction, Vec<String>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(KeyBindings::from_saved(&saved), bindings);
+
+        // missing actions get their defaults, unknown keys are dropped
+        let saved: HashMap<KeyAction, Vec<String>> =
+            serde_json::from_str(r#"{"Undo": ["Z", "Unknown"]}"#).unwrap();
+        let bindings = KeyBindings::from_saved(&saved);
+        assert_eq!(bindings.keys(KeyAction::Undo), &[KeyCombo::new(KeyCode::Z)]);
+        assert_eq!(bindings.keys(KeyAction::Redo).len(), 3);
+    }
+
+    #[test]
+    fn test_key_names() {
+        for (key, name) in KEY_NAMES {
+            assert_eq!(key_from_name(name), Some(key));
+            assert_eq!(key_name(key), Some(name));
+        }
+    }
+}
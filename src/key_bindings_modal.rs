@@ -0,0 +1,174 @@
+use crate::key_bindings::{BindError, KeyAction};
+use crate::text_button::TextButton;
+
+// The actions are shown a page at a time.
+const ROWS_PER_PAGE: usize = 9;
+
+pub struct KeyBindingsModal {
+    pub show: bool,
+    pub x: f32,
+    pub y: f32,
+    pub game_square: f32,
+    pub width: f32,
+    pub height: f32,
+    pub title_height: f32,
+    pub message_y: f32,
+    pub page: usize,
+    pub rows: Vec<TextButton>,
+    // Next to each row, for a key to go with the ones the action has.
+    pub adds: Vec<TextButton>,
+    pub previous: TextButton,
+    pub next: TextButton,
+    pub reset: TextButton,
+    // The action whose row was clicked, until a key is pressed for it.
+    pub waiting: Option<KeyAction>,
+    // Whether the key goes with the action's keys or replaces them.
+    pub adding: bool,
+    pub error: Option<BindError>,
+}
+
+impl Default for KeyBindingsModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyBindingsModal {
+    pub fn new() -> Self {
+        KeyBindingsModal {
+            show: false,
+            x: 0.0,
+            y: 0.0,
+            game_square: 0.0,
+            width: 0.0,
+            height: 0.0,
+            title_height: 0.0,
+            message_y: 0.0,
+            page: 0,
+            rows: vec![],
+            adds: vec![],
+            previous: Default::default(),
+            next: Default::default(),
+            reset: Default::default(),
+            waiting: None,
+            adding: false,
+            error: None,
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.show = true;
+        self.page = 0;
+        self.waiting = None;
+        self.error = None;
+
+        self.update_key_bindings();
+    }
+
+    pub fn hide(&mut self) {
+        self.show = false;
+    }
+
+    pub fn update(&mut self, game_square: f32) {
+        self.game_square = game_square;
+
+        if self.show {
+            self.update_key_bindings();
+        }
+    }
+
+    pub fn page_count(&self) -> usize {
+        KeyAction::ALL.len().div_ceil(ROWS_PER_PAGE)
+    }
+
+    // The actions on the current page, one for each row.
+    pub fn actions(&self) -> &'static [KeyAction] {
+        let first = self.page * ROWS_PER_PAGE;
+        let last = (first + ROWS_PER_PAGE).min(KeyAction::ALL.len());
+        &KeyAction::ALL[first..last]
+    }
+
+    pub fn next_page(&mut self) {
+        self.page = (self.page + 1) % self.page_count();
+        self.waiting = None;
+        self.update_key_bindings();
+    }
+
+    pub fn previous_page(&mut self) {
+        self.page = (self.page + self.page_count() - 1) % self.page_count();
+        self.waiting = None;
+        self.update_key_bindings();
+    }
+
+    pub fn update_key_bindings(&mut self) {
+        let cell_width = self.game_square / 9.0;
+        let row_height = cell_width * 0.65;
+
+        // every page is as tall as a full one, so the buttons stay put,
+        // and wide enough for actions with a few keys
+        self.width = cell_width * 8.0;
+        self.x = self.game_square / 2.0 - (self.width / 2.0);
+        self.title_height = cell_width * 0.75;
+        self.height =
+            self.title_height + ((ROWS_PER_PAGE + 1) as f32 * row_height) + cell_width * 0.6;
+        self.y = self.game_square / 2.0 - (self.height / 2.0);
+
+        let mut button = TextButton::new();
+        let add_size = cell_width * 0.5;
+        let gap = cell_width * 0.15;
+        self.rows.clear();
+        self.adds.clear();
+        for i in 0..self.actions().len() {
+            let row_y = self.y + self.title_height + (i as f32 * row_height);
+            button.update(
+                self.x + (cell_width * 0.25),
+                row_y,
+                self.width - (cell_width * 0.5) - add_size - gap,
+                cell_width * 0.5,
+            );
+            self.rows.push(button);
+
+            button.update(
+                self.x + self.width - (cell_width * 0.25) - add_size,
+                row_y,
+                add_size,
+                add_size,
+            );
+            self.adds.push(button);
+        }
+
+        let buttons_y = self.y + self.title_height + (ROWS_PER_PAGE as f32 * row_height);
+        let button_width = (self.width - (cell_width * 0.5) - (gap * 2.0)) / 3.0;
+        let buttons = [&mut self.previous, &mut self.next, &mut self.reset];
+        for (i, button) in buttons.into_iter().enumerate() {
+            button.update(
+                self.x + (cell_width * 0.25) + (i as f32 * (button_width + gap)),
+                buttons_y,
+                button_width,
+                cell_width * 0.5,
+            );
+        }
+
+        self.message_y = buttons_y + row_height + (cell_width * 0.25);
+    }
+
+    // The action whose row was clicked.
+    pub fn click_row(&self, x: f32, y: f32) -> Option<KeyAction> {
+        self.rows
+            .iter()
+            .position(|row| row.click(x, y))
+            .map(|row| self.actions()[row])
+    }
+
+    // The action whose add button was clicked.
+    pub fn click_add(&self, x: f32, y: f32) -> Option<KeyAction> {
+        self.adds
+            .iter()
+            .position(|add| add.click(x, y))
+            .map(|row| self.actions()[row])
+    }
+
+    pub fn click_outside(&mut self, x: f32, y: f32) -> bool {
+        x < self.x || x > self.x + self.width || y < self.y || y > self.y + self.height
+    }
+}
@@ -0,0 +1,163 @@
+use crate::index::xy_to_index;
+use crate::logic::house::peers;
+use crate::solver::digit_mask;
+
+pub const ALL_CANDIDATES: u16 = 0x1FF;
+
+pub fn mask_digits(mask: u16) -> impl Iterator<Item = u8> {
+    (1..=9).filter(move |digit| mask & digit_mask(*digit) != 0)
+}
+
+// A grid of placed values plus the pencil candidates of every empty cell.
+// Candidates only ever shrink while solving, a placed value removes
+// itself from all of its peers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandidateGrid {
+    pub values: [u8; 81],
+    pub candidates: [u16; 81],
+}
+
+impl Default for CandidateGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CandidateGrid {
+    pub fn new() -> Self {
+        CandidateGrid {
+            values: [0; 81],
+            candidates: [ALL_CANDIDATES; 81],
+        }
+    }
+
+    pub fn from_values(values: &[u8; 81]) -> Self {
+        let mut grid = CandidateGrid::new();
+        for (index, value) in values.iter().enumerate() {
+            if *value != 0 {
+                grid.set_value(index, *value);
+            }
+        }
+        grid
+    }
+
+    pub fn from_board(board: &[[u8; 9]; 9]) -> Self {
+        let mut values = [0; 81];
+        for (y, row) in board.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                values[xy_to_index(x, y, 9)] = *value;
+            }
+        }
+        CandidateGrid::from_values(&values)
+    }
+
+    pub fn set_value(&mut self, index: usize, value: u8) {
+        self.values[index] = value;
+        self.candidates[index] = 0;
+
+        for peer in peers(index) {
+            self.candidates[peer] &= !digit_mask(value);
+        }
+    }
+
+    pub fn has_candidate(&self, index: usize, digit: u8) -> bool {
+        self.candidates[index] & digit_mask(digit) != 0
+    }
+
+    pub fn remove_candidate(&mut self, index: usize, digit: u8) -> bool {
+        let had = self.has_candidate(index, digit);
+        self.candidates[index] &= !digit_mask(digit);
+        had
+    }
+
+    pub fn candidate_count(&self, index: usize) -> u32 {
+        self.candidates[index].count_ones()
+    }
+
+    pub fn is_empty(&self, index: usize) -> bool {
+        self.values[index] == 0
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.values.iter().all(|value| *value != 0)
+    }
+
+    // An empty cell without candidates means an earlier placement was wrong.
+    pub fn has_contradiction(&self) -> bool {
+        (0..81).any(|index| self.is_empty(index) && self.candidates[index] == 0)
+    }
+
+    // Cells of the house that still have the digit as a candidate.
+    pub fn positions(&self, house: &[usize; 9], digit: u8) -> Vec<usize> {
+        house
+            .iter()
+            .copied()
+            .filter(|index| self.has_candidate(*index, digit))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::index_to_xy;
+
+    fn to_board(grid: &CandidateGrid) -> [[u8; 9]; 9] {
+        let mut board = [[0; 9]; 9];
+        for (index, value) in grid.values.iter().enumerate() {
+            let (x, y) = index_to_xy(index, 9);
+            board[y][x] = *value;
+        }
+        board
+    }
+
+    #[test]
+    fn empty_grid() {
+        let grid = CandidateGrid::new();
+        assert!(!grid.is_solved());
+        assert!(!grid.has_contradiction());
+        for index in 0..81 {
+            assert_eq!(grid.candidate_count(index), 9);
+        }
+    }
+
+    #[test]
+    fn set_value_updates_peers() {
+        let mut grid = CandidateGrid::new();
+        grid.set_value(0, 5);
+
+        assert_eq!(grid.values[0], 5);
+        assert_eq!(grid.candidate_count(0), 0);
+        assert!(!grid.has_candidate(8, 5));
+        assert!(!grid.has_candidate(72, 5));
+        assert!(!grid.has_candidate(20, 5));
+        assert!(grid.has_candidate(21, 5));
+        assert!(grid.has_candidate(20, 4));
+    }
+
+    #[test]
+    fn board_round_trip() {
+        let mut board = [[0; 9]; 9];
+        board[0][1] = 3;
+        board[8][7] = 9;
+
+        let grid = CandidateGrid::from_board(&board);
+        assert_eq!(grid.values[1], 3);
+        assert_eq!(grid.values[79], 9);
+        assert_eq!(to_board(&grid), board);
+    }
+
+    #[test]
+    fn candidate_removal() {
+        let mut grid = CandidateGrid::new();
+        assert!(grid.remove_candidate(10, 4));
+        assert!(!grid.remove_candidate(10, 4));
+        assert_eq!(mask_digits(grid.candidates[10]).count(), 8);
+        assert_eq!(grid.positions(&crate::ROW_INDEXES[1], 4).len(), 8);
+
+        for digit in 1..=9 {
+            grid.remove_candidate(10, digit);
+        }
+        assert!(grid.has_contradiction());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::logic::candidate_grid::CandidateGrid;
+use crate::logic::house::{sees, House};
+use crate::logic::solve_step::{SolveStep, Technique};
+
+// Houses where the digit has exactly two spots link those two cells,
+// one of them has to be the digit and the other can't be.
+fn conjugate_pairs(grid: &CandidateGrid, digit: u8) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    for house in House::all() {
+        let positions = grid.positions(house.indexes(), digit);
+        if positions.len() == 2 && !pairs.contains(&(positions[0], positions[1])) {
+            pairs.push((positions[0], positions[1]));
+        }
+    }
+    pairs
+}
+
+// Splits the chains of conjugate pairs into two colors each.
+fn color_chains(pairs: &[(usize, usize)]) -> Vec<[Vec<usize>; 2]> {
+    let mut visited = [false; 81];
+    let mut chains: Vec<[Vec<usize>; 2]> = vec![];
+
+    for (start, _) in pairs.iter() {
+        if visited[*start] {
+            continue;
+        }
+
+        let chain = chains.len();
+        chains.push([vec![], vec![]]);
+
+        let mut stack = vec![(*start, 0)];
+        while let Some((index, color)) = stack.pop() {
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            chains[chain][color].push(index);
+
+            for (a, b) in pairs.iter() {
+                if *a == index {
+                    stack.push((*b, 1 - color));
+                } else if *b == index {
+                    stack.push((*a, 1 - color));
+                }
+            }
+        }
+
+        chains[chain][0].sort();
+        chains[chain][1].sort();
+    }
+
+    chains
+}
+
+// Either all cells of one color are the digit or all of the other color are.
+// If two cells of the same color see each other that color is wrong, and a
+// cell that sees both colors can't be the digit.
+pub fn find_simple_coloring(grid: &CandidateGrid) -> Option<SolveStep> {
+    for digit in 1..=9 {
+        let pairs = conjugate_pairs(grid, digit);
+
+        for chain in color_chains(&pairs) {
+            let cells: Vec<usize> = chain.iter().flatten().copied().collect();
+            if cells.len() < 3 {
+                continue;
+            }
+
+            let mut eliminations = vec![];
+            for color in chain.iter() {
+                let wrapped = color.iter().any(|a| color.iter().any(|b| sees(*a, *b)));
+                if wrapped {
+                    eliminations = color.iter().map(|index| (*index, digit)).collect();
+                    break;
+                }
+            }
+
+            if eliminations.is_empty() {
+                eliminations = (0..81)
+                    .filter(|index| {
+                        grid.has_candidate(*index, digit)
+                            && !cells.contains(index)
+                            && chain[0].iter().any(|a| sees(*index, *a))
+                            && chain[1].iter().any(|b| sees(*index, *b))
+                    })
+                    .map(|index| (index, digit))
+                    .collect();
+            }
+
+            let mut cells = cells;
+            cells.sort();
+            let step = SolveStep::elimination(
+                Technique::SimpleColoring,
+                vec![],
+                cells,
+                vec![digit],
+                eliminations,
+            );
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keep_digit(grid: &mut CandidateGrid, cells: &[usize], digit: u8) {
+        for index in 0..81 {
+            if !cells.contains(&index) {
+                grid.remove_candidate(index, digit);
+            }
+        }
+    }
+
+    #[test]
+    fn color_trap() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_simple_coloring(&grid), None);
+
+        // chain r1c1 - r1c5 - r5c5 - r4c6, the ends get different colors
+        // and r4c1 sees both of them, r8c1 and r4c9 only break up the houses
+        keep_digit(&mut grid, &[0, 4, 40, 32, 27, 63, 35], 7);
+
+        let step = find_simple_coloring(&grid).unwrap();
+        assert_eq!(step.technique, Technique::SimpleColoring);
+        assert_eq!(step.cells, vec![0, 4, 32, 40]);
+        assert_eq!(step.eliminations, vec![(27, 7)]);
+    }
+
+    #[test]
+    fn color_wrap() {
+        let mut grid = CandidateGrid::new();
+
+        // chain r1c1 - r1c5 - r5c5 - r5c2 - r2c2, r1c1 and r2c2 share a color
+        // but they also share a box, so that color is false
+        keep_digit(&mut grid, &[0, 4, 40, 37, 10, 20], 3);
+
+        let step = find_simple_coloring(&grid).unwrap();
+        assert_eq!(step.technique, Technique::SimpleColoring);
+        assert_eq!(step.eliminations, vec![(0, 3), (10, 3), (40, 3)]);
+    }
+}
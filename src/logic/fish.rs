@@ -0,0 +1,147 @@
+use crate::logic::candidate_grid::CandidateGrid;
+use crate::logic::house::{column_of, row_of, House};
+use crate::logic::solve_step::{SolveStep, Technique};
+use crate::logic::subsets::combinations;
+
+// N rows where the digit only fits in the same N columns. Whichever way the
+// digit is placed it takes up those columns, so it can be removed from the
+// rest of them. The same works with rows and columns swapped.
+fn find_fish(grid: &CandidateGrid, size: usize, technique: Technique) -> Option<SolveStep> {
+    for digit in 1..=9 {
+        for by_row in [true, false] {
+            let base_house = |line| {
+                if by_row {
+                    House::Row(line)
+                } else {
+                    House::Column(line)
+                }
+            };
+            let cover_house = |line| {
+                if by_row {
+                    House::Column(line)
+                } else {
+                    House::Row(line)
+                }
+            };
+            let cover_line = |index| {
+                if by_row {
+                    column_of(index)
+                } else {
+                    row_of(index)
+                }
+            };
+
+            let lines: Vec<usize> = (0..9)
+                .filter(|line| {
+                    let count = grid.positions(base_house(*line).indexes(), digit).len();
+                    count >= 2 && count <= size
+                })
+                .collect();
+
+            for base in combinations(&lines, size) {
+                let mut cells = vec![];
+                let mut covers = vec![];
+                for line in base.iter() {
+                    for index in grid.positions(base_house(*line).indexes(), digit) {
+                        cells.push(index);
+                        if !covers.contains(&cover_line(index)) {
+                            covers.push(cover_line(index));
+                        }
+                    }
+                }
+                if covers.len() != size {
+                    continue;
+                }
+                covers.sort();
+
+                let mut eliminations = vec![];
+                for cover in covers.iter() {
+                    for index in grid.positions(cover_house(*cover).indexes(), digit) {
+                        if !cells.contains(&index) {
+                            eliminations.push((index, digit));
+                        }
+                    }
+                }
+
+                cells.sort();
+                let houses = base
+                    .iter()
+                    .map(|line| base_house(*line))
+                    .chain(covers.iter().map(|line| cover_house(*line)))
+                    .collect();
+
+                let step =
+                    SolveStep::elimination(technique, houses, cells, vec![digit], eliminations);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+pub fn find_x_wing(grid: &CandidateGrid) -> Option<SolveStep> {
+    find_fish(grid, 2, Technique::XWing)
+}
+
+pub fn find_swordfish(grid: &CandidateGrid) -> Option<SolveStep> {
+    find_fish(grid, 3, Technique::Swordfish)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keep_in_row(grid: &mut CandidateGrid, row: usize, columns: &[usize], digit: u8) {
+        for col in 0..9 {
+            if !columns.contains(&col) {
+                grid.remove_candidate(row * 9 + col, digit);
+            }
+        }
+    }
+
+    #[test]
+    fn x_wing() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_x_wing(&grid), None);
+
+        keep_in_row(&mut grid, 1, &[2, 7], 4);
+        keep_in_row(&mut grid, 5, &[2, 7], 4);
+
+        let step = find_x_wing(&grid).unwrap();
+        assert_eq!(step.technique, Technique::XWing);
+        assert_eq!(
+            step.houses,
+            vec![
+                House::Row(1),
+                House::Row(5),
+                House::Column(2),
+                House::Column(7)
+            ]
+        );
+        assert_eq!(step.cells, vec![11, 16, 47, 52]);
+        assert_eq!(step.digits, vec![4]);
+        assert_eq!(step.eliminations.len(), 14);
+        assert!(step.eliminations.contains(&(2, 4)));
+        assert!(!step.eliminations.contains(&(11, 4)));
+    }
+
+    #[test]
+    fn swordfish() {
+        let mut grid = CandidateGrid::new();
+        keep_in_row(&mut grid, 0, &[0, 4], 9);
+        keep_in_row(&mut grid, 3, &[4, 8], 9);
+        keep_in_row(&mut grid, 7, &[0, 8], 9);
+
+        assert_eq!(find_x_wing(&grid), None);
+
+        let step = find_swordfish(&grid).unwrap();
+        assert_eq!(step.technique, Technique::Swordfish);
+        assert_eq!(step.cells, vec![0, 4, 31, 35, 63, 71]);
+        assert_eq!(step.eliminations.len(), 18);
+        assert!(step.eliminations.contains(&(9, 9)));
+        assert!(!step.eliminations.contains(&(63, 9)));
+    }
+}
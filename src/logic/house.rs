@@ -0,0 +1,106 @@
+use crate::{BOX_INDEXES, COLUMN_INDEXES, ROW_INDEXES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum House {
+    Row(usize),
+    Column(usize),
+    Box(usize),
+}
+
+impl House {
+    pub fn all() -> impl Iterator<Item = House> {
+        (0..9)
+            .map(House::Row)
+            .chain((0..9).map(House::Column))
+            .chain((0..9).map(House::Box))
+    }
+
+    pub fn indexes(&self) -> &'static [usize; 9] {
+        match *self {
+            House::Row(i) => &ROW_INDEXES[i],
+            House::Column(i) => &COLUMN_INDEXES[i],
+            House::Box(i) => &BOX_INDEXES[i],
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match *self {
+            House::Row(i) => format!("row {}", i + 1),
+            House::Column(i) => format!("column {}", i + 1),
+            House::Box(i) => format!("box {}", i + 1),
+        }
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        match *self {
+            House::Row(i) => row_of(index) == i,
+            House::Column(i) => column_of(index) == i,
+            House::Box(i) => box_of(index) == i,
+        }
+    }
+}
+
+// Cells are named like r3c5, rows and columns counted from 1.
+pub fn cell_name(index: usize) -> String {
+    format!("r{}c{}", row_of(index) + 1, column_of(index) + 1)
+}
+
+pub fn row_of(index: usize) -> usize {
+    index / 9
+}
+
+pub fn column_of(index: usize) -> usize {
+    index % 9
+}
+
+pub fn box_of(index: usize) -> usize {
+    (row_of(index) / 3) * 3 + (column_of(index) / 3)
+}
+
+pub fn sees(a: usize, b: usize) -> bool {
+    a != b && (row_of(a) == row_of(b) || column_of(a) == column_of(b) || box_of(a) == box_of(b))
+}
+
+pub fn peers(index: usize) -> impl Iterator<Item = usize> {
+    (0..81).filter(move |other| sees(index, *other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn house_indexes() {
+        assert_eq!(House::all().count(), 27);
+
+        for house in House::all() {
+            for index in house.indexes() {
+                assert!(house.contains(*index));
+            }
+            assert_eq!((0..81).filter(|i| house.contains(*i)).count(), 9);
+        }
+    }
+
+    #[test]
+    fn cell_peers() {
+        for index in 0..81 {
+            assert_eq!(peers(index).count(), 20);
+        }
+
+        assert!(sees(0, 8));
+        assert!(sees(0, 72));
+        assert!(sees(0, 20));
+        assert!(!sees(0, 0));
+        assert!(!sees(0, 21));
+        assert_eq!(box_of(80), 8);
+        assert_eq!(box_of(30), 4);
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(House::Row(2).name(), "row 3");
+        assert_eq!(House::Box(8).name(), "box 9");
+        assert_eq!(cell_name(0), "r1c1");
+        assert_eq!(cell_name(22), "r3c5");
+    }
+}
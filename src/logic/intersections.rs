@@ -0,0 +1,144 @@
+use crate::logic::candidate_grid::CandidateGrid;
+use crate::logic::house::{box_of, column_of, row_of, House};
+use crate::logic::solve_step::{SolveStep, Technique};
+
+fn eliminate_outside(
+    grid: &CandidateGrid,
+    target: House,
+    source: House,
+    digit: u8,
+) -> Vec<(usize, u8)> {
+    target
+        .indexes()
+        .iter()
+        .copied()
+        .filter(|index| !source.contains(*index) && grid.has_candidate(*index, digit))
+        .map(|index| (index, digit))
+        .collect()
+}
+
+// Inside a box the digit is locked to one row or column,
+// so it can't be anywhere else on that line.
+pub fn find_pointing_pair(grid: &CandidateGrid) -> Option<SolveStep> {
+    for b in 0..9 {
+        let source = House::Box(b);
+        for digit in 1..=9 {
+            let positions = grid.positions(source.indexes(), digit);
+            if positions.len() < 2 {
+                continue;
+            }
+
+            let lines = [
+                House::Row(row_of(positions[0])),
+                House::Column(column_of(positions[0])),
+            ];
+            for target in lines {
+                if !positions.iter().all(|index| target.contains(*index)) {
+                    continue;
+                }
+
+                let step = SolveStep::elimination(
+                    Technique::PointingPair,
+                    vec![source, target],
+                    positions.clone(),
+                    vec![digit],
+                    eliminate_outside(grid, target, source, digit),
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// On a row or column the digit is locked inside one box,
+// so it can't be anywhere else in that box.
+pub fn find_box_line_reduction(grid: &CandidateGrid) -> Option<SolveStep> {
+    let lines = (0..9).map(House::Row).chain((0..9).map(House::Column));
+    for source in lines {
+        for digit in 1..=9 {
+            let positions = grid.positions(source.indexes(), digit);
+            if positions.len() < 2 {
+                continue;
+            }
+
+            let b = box_of(positions[0]);
+            if !positions.iter().all(|index| box_of(*index) == b) {
+                continue;
+            }
+
+            let target = House::Box(b);
+            let step = SolveStep::elimination(
+                Technique::BoxLineReduction,
+                vec![source, target],
+                positions,
+                vec![digit],
+                eliminate_outside(grid, target, source, digit),
+            );
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointing_pair() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_pointing_pair(&grid), None);
+
+        // 5 in box 0 only on the first row
+        for index in [9, 10, 11, 18, 19, 20] {
+            grid.remove_candidate(index, 5);
+        }
+
+        let step = find_pointing_pair(&grid).unwrap();
+        assert_eq!(step.technique, Technique::PointingPair);
+        assert_eq!(step.houses, vec![House::Box(0), House::Row(0)]);
+        assert_eq!(step.cells, vec![0, 1, 2]);
+        assert_eq!(
+            step.eliminations,
+            (3..9).map(|index| (index, 5)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn pointing_pair_column() {
+        let mut grid = CandidateGrid::new();
+        for index in [0, 1, 9, 10, 18, 19] {
+            grid.remove_candidate(index, 8);
+        }
+
+        let step = find_pointing_pair(&grid).unwrap();
+        assert_eq!(step.houses, vec![House::Box(0), House::Column(2)]);
+        assert_eq!(step.eliminations.len(), 6);
+        assert!(step.eliminations.contains(&(74, 8)));
+    }
+
+    #[test]
+    fn box_line_reduction() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_box_line_reduction(&grid), None);
+
+        // 2 in the first row only inside box 2
+        for index in 0..6 {
+            grid.remove_candidate(index, 2);
+        }
+
+        let step = find_box_line_reduction(&grid).unwrap();
+        assert_eq!(step.technique, Technique::BoxLineReduction);
+        assert_eq!(step.houses, vec![House::Row(0), House::Box(2)]);
+        assert_eq!(step.cells, vec![6, 7, 8]);
+        assert_eq!(step.eliminations.len(), 6);
+        assert!(step.eliminations.contains(&(26, 2)));
+    }
+}
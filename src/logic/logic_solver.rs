@@ -0,0 +1,140 @@
+use crate::logic::candidate_grid::CandidateGrid;
+use crate::logic::coloring::find_simple_coloring;
+use crate::logic::fish::{find_swordfish, find_x_wing};
+use crate::logic::intersections::{find_box_line_reduction, find_pointing_pair};
+use crate::logic::singles::{find_hidden_single, find_naked_single};
+use crate::logic::solve_step::SolveStep;
+use crate::logic::subsets::{
+    find_hidden_pair, find_hidden_triple, find_naked_pair, find_naked_triple,
+};
+use crate::logic::wings::find_xy_wing;
+
+type TechniqueFn = fn(&CandidateGrid) -> Option<SolveStep>;
+
+// Tried in this order, so the first step found is always the simplest one.
+const TECHNIQUES: [TechniqueFn; 12] = [
+    find_naked_single,
+    find_hidden_single,
+    find_naked_pair,
+    find_hidden_pair,
+    find_naked_triple,
+    find_hidden_triple,
+    find_pointing_pair,
+    find_box_line_reduction,
+    find_x_wing,
+    find_swordfish,
+    find_xy_wing,
+    find_simple_coloring,
+];
+
+pub struct LogicSolution {
+    pub steps: Vec<SolveStep>,
+    pub solved: bool,
+}
+
+pub fn find_step(grid: &CandidateGrid) -> Option<SolveStep> {
+    if grid.has_contradiction() {
+        return None;
+    }
+
+    TECHNIQUES.iter().find_map(|technique| technique(grid))
+}
+
+pub fn apply_step(grid: &mut CandidateGrid, step: &SolveStep) {
+    if let Some((index, digit)) = step.placement {
+        grid.set_value(index, digit);
+    }
+
+    for (index, digit) in step.eliminations.iter() {
+        grid.remove_candidate(*index, *digit);
+    }
+}
+
+// Keeps applying the simplest step until the grid is solved or nothing applies.
+pub fn solve_logically(grid: &CandidateGrid) -> LogicSolution {
+    let mut grid = *grid;
+    let mut steps = vec![];
+
+    while !grid.is_solved() {
+        let Some(step) = find_step(&grid) else {
+            break;
+        };
+
+        apply_step(&mut grid, &step);
+        steps.push(step);
+    }
+
+    LogicSolution {
+        steps,
+        solved: grid.is_solved(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::solve_step::Technique;
+
+    const PUZZLE: [[u8; 9]; 9] = [
+        [5, 3, 0, 0, 7, 0, 0, 0, 0],
+        [6, 0, 0, 1, 9, 5, 0, 0, 0],
+        [0, 9, 8, 0, 0, 0, 0, 6, 0],
+        [8, 0, 0, 0, 6, 0, 0, 0, 3],
+        [4, 0, 0, 8, 0, 3, 0, 0, 1],
+        [7, 0, 0, 0, 2, 0, 0, 0, 6],
+        [0, 6, 0, 0, 0, 0, 2, 8, 0],
+        [0, 0, 0, 4, 1, 9, 0, 0, 5],
+        [0, 0, 0, 0, 8, 0, 0, 7, 9],
+    ];
+
+    #[test]
+    fn solve_with_singles() {
+        let grid = CandidateGrid::from_board(&PUZZLE);
+        let solution = solve_logically(&grid);
+
+        assert!(solution.solved);
+        assert_eq!(solution.steps.len(), 51);
+        assert!(solution
+            .steps
+            .iter()
+            .all(|step| step.technique <= Technique::HiddenSingle));
+
+        let mut solved = grid;
+        for step in solution.steps.iter() {
+            apply_step(&mut solved, step);
+        }
+        let mut board = PUZZLE;
+        assert!(crate::solver::solve(&mut board));
+        assert_eq!(solved.values[..], *board.as_flattened());
+    }
+
+    #[test]
+    fn steps_are_ordered() {
+        let mut grid = CandidateGrid::new();
+        for digit in 1..=8 {
+            grid.remove_candidate(40, digit);
+        }
+        for index in 0..8 {
+            grid.remove_candidate(index, 3);
+        }
+
+        let step = find_step(&grid).unwrap();
+        assert_eq!(step.technique, Technique::NakedSingle);
+        apply_step(&mut grid, &step);
+        assert_eq!(grid.values[40], 9);
+
+        let step = find_step(&grid).unwrap();
+        assert_eq!(step.technique, Technique::HiddenSingle);
+    }
+
+    #[test]
+    fn stops_on_contradiction() {
+        let mut grid = CandidateGrid::new();
+        for digit in 1..=9 {
+            grid.remove_candidate(0, digit);
+        }
+
+        assert_eq!(find_step(&grid), None);
+        assert!(!solve_logically(&grid).solved);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod candidate_grid;
+pub mod coloring;
+pub mod fish;
+pub mod house;
+pub mod intersections;
+pub mod logic_solver;
+pub mod singles;
+pub mod solve_step;
+pub mod subsets;
+pub mod wings;
@@ -0,0 +1,73 @@
+use crate::logic::candidate_grid::{mask_digits, CandidateGrid};
+use crate::logic::house::House;
+use crate::logic::solve_step::{SolveStep, Technique};
+
+// A cell with only one candidate left.
+pub fn find_naked_single(grid: &CandidateGrid) -> Option<SolveStep> {
+    for index in 0..81 {
+        if grid.is_empty(index) && grid.candidate_count(index) == 1 {
+            let digit = mask_digits(grid.candidates[index]).next()?;
+            return Some(SolveStep::placement(
+                Technique::NakedSingle,
+                None,
+                index,
+                digit,
+            ));
+        }
+    }
+
+    None
+}
+
+// A digit that fits in only one cell of a house.
+pub fn find_hidden_single(grid: &CandidateGrid) -> Option<SolveStep> {
+    for house in House::all() {
+        for digit in 1..=9 {
+            let positions = grid.positions(house.indexes(), digit);
+            if positions.len() == 1 {
+                return Some(SolveStep::placement(
+                    Technique::HiddenSingle,
+                    Some(house),
+                    positions[0],
+                    digit,
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn naked_single() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_naked_single(&grid), None);
+
+        for digit in 1..=8 {
+            grid.remove_candidate(40, digit);
+        }
+
+        let step = find_naked_single(&grid).unwrap();
+        assert_eq!(step.technique, Technique::NakedSingle);
+        assert_eq!(step.placement, Some((40, 9)));
+    }
+
+    #[test]
+    fn hidden_single() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_hidden_single(&grid), None);
+
+        for index in 0..8 {
+            grid.remove_candidate(index, 3);
+        }
+
+        let step = find_hidden_single(&grid).unwrap();
+        assert_eq!(step.technique, Technique::HiddenSingle);
+        assert_eq!(step.houses, vec![House::Row(0)]);
+        assert_eq!(step.placement, Some((8, 3)));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::logic::house::House;
+
+// Ordered from the simplest to the hardest technique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    PointingPair,
+    BoxLineReduction,
+    XWing,
+    Swordfish,
+    XYWing,
+    SimpleColoring,
+}
+
+impl Technique {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked single",
+            Technique::HiddenSingle => "Hidden single",
+            Technique::NakedPair => "Naked pair",
+            Technique::HiddenPair => "Hidden pair",
+            Technique::NakedTriple => "Naked triple",
+            Technique::HiddenTriple => "Hidden triple",
+            Technique::PointingPair => "Pointing pair",
+            Technique::BoxLineReduction => "Box/line reduction",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::SimpleColoring => "Simple coloring",
+        }
+    }
+}
+
+// A single deduction. `cells` are the cells that make up the pattern,
+// `houses` the rows, columns and boxes it was found in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveStep {
+    pub technique: Technique,
+    pub houses: Vec<House>,
+    pub cells: Vec<usize>,
+    pub digits: Vec<u8>,
+    pub eliminations: Vec<(usize, u8)>,
+    pub placement: Option<(usize, u8)>,
+}
+
+impl SolveStep {
+    pub fn placement(technique: Technique, house: Option<House>, index: usize, digit: u8) -> Self {
+        SolveStep {
+            technique,
+            houses: house.into_iter().collect(),
+            cells: vec![index],
+            digits: vec![digit],
+            eliminations: vec![],
+            placement: Some((index, digit)),
+        }
+    }
+
+    pub fn elimination(
+        technique: Technique,
+        houses: Vec<House>,
+        cells: Vec<usize>,
+        digits: Vec<u8>,
+        eliminations: Vec<(usize, u8)>,
+    ) -> Option<Self> {
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        Some(SolveStep {
+            technique,
+            houses,
+            cells,
+            digits,
+            eliminations,
+            placement: None,
+        })
+    }
+}
@@ -0,0 +1,226 @@
+use crate::logic::candidate_grid::{mask_digits, CandidateGrid};
+use crate::logic::house::House;
+use crate::logic::solve_step::{SolveStep, Technique};
+use crate::solver::digit_mask;
+
+pub fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    let mut result = vec![];
+    for (i, item) in items.iter().enumerate() {
+        if items.len() - i < size {
+            break;
+        }
+
+        for mut rest in combinations(&items[i + 1..], size - 1) {
+            rest.insert(0, *item);
+            result.push(rest);
+        }
+    }
+
+    result
+}
+
+// N cells in a house that together only hold N candidates,
+// those digits can be removed from the rest of the house.
+fn find_naked_subset(grid: &CandidateGrid, size: usize, technique: Technique) -> Option<SolveStep> {
+    for house in House::all() {
+        let cells: Vec<usize> = house
+            .indexes()
+            .iter()
+            .copied()
+            .filter(|index| {
+                let count = grid.candidate_count(*index) as usize;
+                grid.is_empty(*index) && count >= 2 && count <= size
+            })
+            .collect();
+
+        for subset in combinations(&cells, size) {
+            let mask = subset
+                .iter()
+                .fold(0, |mask, index| mask | grid.candidates[*index]);
+            if mask.count_ones() as usize != size {
+                continue;
+            }
+
+            let mut eliminations = vec![];
+            for index in house.indexes() {
+                if subset.contains(index) {
+                    continue;
+                }
+                for digit in mask_digits(mask & grid.candidates[*index]) {
+                    eliminations.push((*index, digit));
+                }
+            }
+
+            let step = SolveStep::elimination(
+                technique,
+                vec![house],
+                subset,
+                mask_digits(mask).collect(),
+                eliminations,
+            );
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+
+    None
+}
+
+// N digits that only fit in the same N cells of a house,
+// every other candidate can be removed from those cells.
+fn find_hidden_subset(
+    grid: &CandidateGrid,
+    size: usize,
+    technique: Technique,
+) -> Option<SolveStep> {
+    for house in House::all() {
+        let digits: Vec<u8> = (1..=9)
+            .filter(|digit| {
+                let count = grid.positions(house.indexes(), *digit).len();
+                count >= 2 && count <= size
+            })
+            .collect();
+
+        for subset in combinations(&digits, size) {
+            let mut cells = vec![];
+            for digit in subset.iter() {
+                for index in grid.positions(house.indexes(), *digit) {
+                    if !cells.contains(&index) {
+                        cells.push(index);
+                    }
+                }
+            }
+            if cells.len() != size {
+                continue;
+            }
+
+            cells.sort();
+            let mask = subset
+                .iter()
+                .fold(0, |mask, digit| mask | digit_mask(*digit));
+
+            let mut eliminations = vec![];
+            for index in cells.iter() {
+                for digit in mask_digits(grid.candidates[*index] & !mask) {
+                    eliminations.push((*index, digit));
+                }
+            }
+
+            let step = SolveStep::elimination(technique, vec![house], cells, subset, eliminations);
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+
+    None
+}
+
+pub fn find_naked_pair(grid: &CandidateGrid) -> Option<SolveStep> {
+    find_naked_subset(grid, 2, Technique::NakedPair)
+}
+
+pub fn find_naked_triple(grid: &CandidateGrid) -> Option<SolveStep> {
+    find_naked_subset(grid, 3, Technique::NakedTriple)
+}
+
+pub fn find_hidden_pair(grid: &CandidateGrid) -> Option<SolveStep> {
+    find_hidden_subset(grid, 2, Technique::HiddenPair)
+}
+
+pub fn find_hidden_triple(grid: &CandidateGrid) -> Option<SolveStep> {
+    find_hidden_subset(grid, 3, Technique::HiddenTriple)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keep_only(grid: &mut CandidateGrid, index: usize, digits: &[u8]) {
+        grid.candidates[index] = digits.iter().fold(0, |mask, d| mask | digit_mask(*d));
+    }
+
+    #[test]
+    fn combination_count() {
+        assert_eq!(combinations(&[1, 2, 3, 4], 2).len(), 6);
+        assert_eq!(combinations(&[1, 2, 3, 4], 3).len(), 4);
+        assert_eq!(combinations(&[1, 2], 3).len(), 0);
+        assert_eq!(combinations(&[1, 2, 3], 3), vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn naked_pair() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_naked_pair(&grid), None);
+
+        keep_only(&mut grid, 0, &[1, 2]);
+        keep_only(&mut grid, 5, &[1, 2]);
+
+        let step = find_naked_pair(&grid).unwrap();
+        assert_eq!(step.technique, Technique::NakedPair);
+        assert_eq!(step.houses, vec![House::Row(0)]);
+        assert_eq!(step.cells, vec![0, 5]);
+        assert_eq!(step.digits, vec![1, 2]);
+        assert_eq!(step.eliminations.len(), 14);
+        assert!(step.eliminations.contains(&(8, 2)));
+    }
+
+    #[test]
+    fn naked_triple() {
+        let mut grid = CandidateGrid::new();
+        keep_only(&mut grid, 0, &[1, 2]);
+        keep_only(&mut grid, 9, &[2, 3]);
+        keep_only(&mut grid, 18, &[1, 3]);
+
+        assert_eq!(find_naked_pair(&grid), None);
+
+        let step = find_naked_triple(&grid).unwrap();
+        assert_eq!(step.houses, vec![House::Column(0)]);
+        assert_eq!(step.cells, vec![0, 9, 18]);
+        assert_eq!(step.digits, vec![1, 2, 3]);
+        assert!(step.eliminations.contains(&(72, 3)));
+        assert!(!step.eliminations.contains(&(72, 4)));
+    }
+
+    #[test]
+    fn hidden_pair() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_hidden_pair(&grid), None);
+
+        for index in 2..9 {
+            grid.remove_candidate(index, 6);
+            grid.remove_candidate(index, 7);
+        }
+
+        let step = find_hidden_pair(&grid).unwrap();
+        assert_eq!(step.technique, Technique::HiddenPair);
+        assert_eq!(step.cells, vec![0, 1]);
+        assert_eq!(step.digits, vec![6, 7]);
+        assert_eq!(step.eliminations.len(), 14);
+        assert!(!step.eliminations.contains(&(0, 6)));
+    }
+
+    #[test]
+    fn hidden_triple() {
+        let mut grid = CandidateGrid::new();
+        for index in [0, 1, 2, 9, 10, 11] {
+            grid.remove_candidate(index, 4);
+            grid.remove_candidate(index, 5);
+            grid.remove_candidate(index, 6);
+        }
+        grid.remove_candidate(18, 6);
+        grid.remove_candidate(19, 4);
+        grid.remove_candidate(20, 5);
+
+        let step = find_hidden_triple(&grid).unwrap();
+        assert_eq!(step.houses, vec![House::Box(0)]);
+        assert_eq!(step.cells, vec![18, 19, 20]);
+        assert_eq!(step.digits, vec![4, 5, 6]);
+        assert!(step.eliminations.contains(&(18, 1)));
+    }
+}
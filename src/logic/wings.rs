@@ -0,0 +1,97 @@
+use crate::logic::candidate_grid::{mask_digits, CandidateGrid};
+use crate::logic::house::{peers, sees};
+use crate::logic::solve_step::{SolveStep, Technique};
+
+fn bivalue_cells(grid: &CandidateGrid) -> Vec<usize> {
+    (0..81)
+        .filter(|index| grid.is_empty(*index) && grid.candidate_count(*index) == 2)
+        .collect()
+}
+
+// A pivot with candidates XY that sees two pincers XZ and YZ. Either pincer
+// ends up being Z, so any cell that sees both pincers can't be Z.
+pub fn find_xy_wing(grid: &CandidateGrid) -> Option<SolveStep> {
+    let bivalues = bivalue_cells(grid);
+
+    for pivot in bivalues.iter().copied() {
+        let pivot_mask = grid.candidates[pivot];
+        let wings: Vec<usize> = bivalues
+            .iter()
+            .copied()
+            .filter(|wing| {
+                let shared = grid.candidates[*wing] & pivot_mask;
+                sees(pivot, *wing) && shared.count_ones() == 1
+            })
+            .collect();
+
+        for (i, first) in wings.iter().copied().enumerate() {
+            for second in wings[i + 1..].iter().copied() {
+                let first_mask = grid.candidates[first];
+                let second_mask = grid.candidates[second];
+
+                // the pincers must share Z but take different halves of the pivot
+                let z_mask = first_mask & second_mask & !pivot_mask;
+                if z_mask.count_ones() != 1 || first_mask & second_mask & pivot_mask != 0 {
+                    continue;
+                }
+
+                let z = mask_digits(z_mask).next()?;
+                let eliminations: Vec<(usize, u8)> = peers(first)
+                    .filter(|index| {
+                        *index != pivot
+                            && *index != second
+                            && sees(*index, second)
+                            && grid.has_candidate(*index, z)
+                    })
+                    .map(|index| (index, z))
+                    .collect();
+
+                let step = SolveStep::elimination(
+                    Technique::XYWing,
+                    vec![],
+                    vec![pivot, first, second],
+                    mask_digits(pivot_mask | z_mask).collect(),
+                    eliminations,
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::digit_mask;
+
+    #[test]
+    fn xy_wing() {
+        let mut grid = CandidateGrid::new();
+        assert_eq!(find_xy_wing(&grid), None);
+
+        // pivot r1c1 {1,2}, pincers r1c7 {1,3} and r5c1 {2,3}
+        grid.candidates[0] = digit_mask(1) | digit_mask(2);
+        grid.candidates[6] = digit_mask(1) | digit_mask(3);
+        grid.candidates[36] = digit_mask(2) | digit_mask(3);
+
+        let step = find_xy_wing(&grid).unwrap();
+        assert_eq!(step.technique, Technique::XYWing);
+        assert_eq!(step.cells, vec![0, 6, 36]);
+        assert_eq!(step.digits, vec![1, 2, 3]);
+        assert_eq!(step.eliminations, vec![(42, 3)]);
+    }
+
+    #[test]
+    fn xy_wing_needs_different_halves() {
+        let mut grid = CandidateGrid::new();
+        grid.candidates[0] = digit_mask(1) | digit_mask(2);
+        grid.candidates[6] = digit_mask(1) | digit_mask(3);
+        grid.candidates[36] = digit_mask(1) | digit_mask(3);
+
+        assert_eq!(find_xy_wing(&grid), None);
+    }
+}
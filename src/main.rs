@@ -16,14 +16,37 @@ mod board;
 mod board_history;
 mod cell_location;
 mod cell_state;
+mod checkpoints_modal;
+mod clipboard;
 mod context;
+mod daily;
 mod draw;
+mod export;
 mod fonts;
+mod game_key;
+mod game_over_modal;
 mod generate;
+mod grade;
+mod hint;
+mod import;
+mod import_modal;
 mod index;
+mod key_bindings;
+mod key_bindings_modal;
+mod logic;
 mod menu;
 mod menu_item;
 mod new_game_modal;
+mod options_modal;
+mod replay;
+mod rng;
+mod settings;
+mod solver;
+mod statistics;
+mod statistics_modal;
+mod symmetry;
+mod text_button;
+mod text_input;
 mod victory_modal;
 
 #[cfg_attr(target_arch = "wasm32", path = "save_wasm.rs")]
@@ -40,20 +63,25 @@ pub const CELL_COLOR_NORMAL: Color = color_u8!(255, 255, 255, 255);
 pub const CELL_COLOR_NORMAL_EMPHASIZE: Color = color_u8!(186, 209, 255, 255);
 pub const CELL_COLOR_PENCIL_EMPHASIZE: Color = color_u8!(255, 193, 140, 255);
 pub const CELL_COLOR_HIGHLIGHTED: Color = color_u8!(219, 219, 219, 255);
+pub const CELL_COLOR_CONFLICT: Color = color_u8!(255, 200, 200, 255);
+pub const CELL_COLOR_HINT: Color = color_u8!(186, 232, 186, 255);
+pub const CELL_CURSOR_COLOR: Color = color_u8!(56, 76, 107, 255);
 
 pub const CELL_TEXT_COLOR: Color = color_u8!(41, 91, 135, 255);
 pub const CELL_TEXT_INITIAL_COLOR: Color = color_u8!(0, 0, 0, 255);
+pub const CELL_TEXT_WRONG_COLOR: Color = color_u8!(220, 40, 40, 255);
 
 pub const MENU_NUMBER_BACKGROUND_NORMAL: Color = color_u8!(56, 76, 107, 255);
 pub const MENU_NUMBER_BACKGROUND_PENCIL: Color = color_u8!(226, 138, 43, 255);
 pub const MENU_DELETE_BACKGROUND: Color = color_u8!(255, 0, 0, 255);
 
 pub const MODAL_BACKGROUND: Color = color_u8!(0, 0, 0, 128);
+pub const MODAL_INPUT_BACKGROUND: Color = color_u8!(235, 235, 235, 255);
+pub const HINT_BACKGROUND: Color = color_u8!(245, 245, 245, 235);
+pub const MODAL_ERROR_TEXT: Color = color_u8!(200, 0, 0, 255);
 pub const MODAL_DIFFICULTY_ONE: Color = color_u8!(0, 128, 0, 255);
 pub const MODAL_DIFFICULTY_TWO: Color = color_u8!(255, 128, 0, 255);
 pub const MODAL_DIFFICULTY_THREE: Color = color_u8!(255, 0, 0, 255);
-pub const MODAL_VICTORY_GOLD: Color = color_u8!(242, 183, 5, 255);
-pub const MODAL_VICTORY_RED: Color = color_u8!(239, 50, 50, 255);
 
 pub const DEBUG_RED: Color = color_u8!(255, 0, 0, 128);
 pub const DEBUG_BLUE: Color = color_u8!(0, 0, 255, 128);
@@ -103,8 +131,6 @@ pub const ICON_DIFFICULTY_2: &str = "\u{f783}";
 pub const ICON_DIFFICULTY_3: &str = "\u{f782}";
 pub const ICON_DIFFICULTY_NEW: &str = "\u{e3fc}";
 pub const ICON_DELETE: &str = "\u{e872}";
-pub const ICON_VICTORY_HEART: &str = "\u{e87d}";
-pub const ICON_VICTORY_STAR: &str = "\u{e8d0}";
 
 pub fn is_legal_number(number: u8) -> bool {
     (1..=9).contains(&number)
@@ -116,7 +142,6 @@ pub fn is_legal_index(number: usize) -> bool {
 
 #[macroquad::main("Sudoku")]
 async fn main() {
-    rand::srand(macroquad::miniquad::date::now() as _);
     let mut context = Context::new(
         "liberation-sans-minimized.ttf",
         "material-font-minimized.ttf",
@@ -16,6 +16,12 @@ pub enum MenuActions {
     Undo = 11,
     New = 12,
     Delete = 13,
+    Options = 14,
+    Hint = 15,
+    Check = 16,
+    Reveal = 17,
+    Redo = 18,
+    Pause = 19,
 }
 
 fn menu_action_from_u8(value: u8) -> Option<MenuActions> {
@@ -52,6 +58,16 @@ pub struct Menu {
     pub undo: MenuItem,
     pub new: MenuItem,
     pub delete: MenuItem,
+    pub options: MenuItem,
+    pub hint: MenuItem,
+    pub check: MenuItem,
+    pub reveal: MenuItem,
+    pub redo: MenuItem,
+    // The strip after the second row that shows the game status.
+    pub status_x: f32,
+    pub status_y: f32,
+    pub status_width: f32,
+    pub status_height: f32,
 }
 
 impl Menu {
@@ -70,6 +86,15 @@ impl Menu {
             undo: Default::default(),
             new: Default::default(),
             delete: Default::default(),
+            options: Default::default(),
+            hint: Default::default(),
+            check: Default::default(),
+            reveal: Default::default(),
+            redo: Default::default(),
+            status_x: 0.0,
+            status_y: 0.0,
+            status_width: 0.0,
+            status_height: 0.0,
         }
     }
 
@@ -106,11 +131,46 @@ impl Menu {
             self.item_size,
         );
 
+        self.redo.update(
+            self.game_padding + (3.0 * self.item_size),
+            self.menu_start_y + second_row_y,
+            self.item_size,
+        );
+
+        self.hint.update(
+            self.game_padding + (4.0 * self.item_size),
+            self.menu_start_y + second_row_y,
+            self.item_size,
+        );
+
+        self.check.update(
+            self.game_padding + (5.0 * self.item_size),
+            self.menu_start_y + second_row_y,
+            self.item_size,
+        );
+
+        self.reveal.update(
+            self.game_padding + (6.0 * self.item_size),
+            self.menu_start_y + second_row_y,
+            self.item_size,
+        );
+
+        self.options.update(
+            self.game_padding + (7.0 * self.item_size),
+            self.menu_start_y + second_row_y,
+            self.item_size,
+        );
+
         self.new.update(
             self.game_padding + (8.0 * self.item_size),
             self.menu_start_y + second_row_y,
             self.item_size,
         );
+
+        self.status_x = self.game_padding;
+        self.status_y = self.menu_start_y + second_row_y + self.item_size;
+        self.status_width = self.board_size;
+        self.status_height = self.item_size / 2.0;
     }
 
     fn update_landscape(&mut self) {
@@ -146,11 +206,46 @@ impl Menu {
             self.item_size,
         );
 
+        self.redo.update(
+            self.menu_start_x + second_row_x,
+            self.game_padding + (3.0 * self.item_size),
+            self.item_size,
+        );
+
+        self.hint.update(
+            self.menu_start_x + second_row_x,
+            self.game_padding + (4.0 * self.item_size),
+            self.item_size,
+        );
+
+        self.check.update(
+            self.menu_start_x + second_row_x,
+            self.game_padding + (5.0 * self.item_size),
+            self.item_size,
+        );
+
+        self.reveal.update(
+            self.menu_start_x + second_row_x,
+            self.game_padding + (6.0 * self.item_size),
+            self.item_size,
+        );
+
+        self.options.update(
+            self.menu_start_x + second_row_x,
+            self.game_padding + (7.0 * self.item_size),
+            self.item_size,
+        );
+
         self.new.update(
             self.menu_start_x + second_row_x,
             self.game_padding + (8.0 * self.item_size),
             self.item_size,
         );
+
+        self.status_x = self.menu_start_x + second_row_x + self.item_size;
+        self.status_y = self.game_padding;
+        self.status_width = self.item_size / 2.0;
+        self.status_height = self.board_size;
     }
 
     pub fn update(&mut self, board_size: f32, game_padding: f32, portrait: bool) {
@@ -185,6 +280,35 @@ impl Menu {
             return Some(MenuActions::Undo);
         }
 
+        if self.redo.click(x, y) {
+            return Some(MenuActions::Redo);
+        }
+
+        // the clock in the status strip pauses the game
+        if x >= self.status_x
+            && x <= self.status_x + self.status_width
+            && y >= self.status_y
+            && y <= self.status_y + self.status_height
+        {
+            return Some(MenuActions::Pause);
+        }
+
+        if self.hint.click(x, y) {
+            return Some(MenuActions::Hint);
+        }
+
+        if self.check.click(x, y) {
+            return Some(MenuActions::Check);
+        }
+
+        if self.reveal.click(x, y) {
+            return Some(MenuActions::Reveal);
+        }
+
+        if self.options.click(x, y) {
+            return Some(MenuActions::Options);
+        }
+
         if self.new.click(x, y) {
             return Some(MenuActions::New);
         }
@@ -1,4 +1,12 @@
+use crate::game_key::GameKey;
 use crate::menu_item::MenuItem;
+use crate::symmetry::Symmetry;
+use crate::text_button::TextButton;
+use crate::text_input::TextInput;
+
+// u64 has 20 digits, keep a digit less so typed seeds always fit,
+// plus the dash and the three option digits of a full game key
+const SEED_MAX_LENGTH: usize = 19 + 4;
 
 pub struct NewGameModal {
     pub show: bool,
@@ -9,9 +17,18 @@ pub struct NewGameModal {
     pub font_width: f32,
     pub width: f32,
     pub height: f32,
+    pub row_height: f32,
     pub difficulty_1: MenuItem,
     pub difficulty_2: MenuItem,
     pub difficulty_3: MenuItem,
+    pub seed: TextButton,
+    pub seed_input: TextInput,
+    pub symmetry: Symmetry,
+    pub symmetry_button: TextButton,
+    pub minimal: bool,
+    pub minimal_button: TextButton,
+    pub daily: TextButton,
+    pub import: TextButton,
 }
 
 impl Default for NewGameModal {
@@ -31,14 +48,27 @@ impl NewGameModal {
             font_width: 0.0,
             width: 0.0,
             height: 0.0,
+            row_height: 0.0,
             difficulty_1: Default::default(),
             difficulty_2: Default::default(),
             difficulty_3: Default::default(),
+            seed: Default::default(),
+            seed_input: TextInput::new(SEED_MAX_LENGTH, |c| c.is_ascii_digit() || c == '-'),
+            symmetry: Symmetry::Rotational,
+            symmetry_button: Default::default(),
+            minimal: false,
+            minimal_button: Default::default(),
+            daily: Default::default(),
+            import: Default::default(),
         }
     }
 
-    pub fn show(&mut self) {
+    // The options start out as the current game's.
+    pub fn show(&mut self, symmetry: Symmetry, minimal: bool) {
         self.show = true;
+        self.seed_input.clear();
+        self.symmetry = symmetry;
+        self.minimal = minimal;
 
         self.update_new_game();
     }
@@ -61,10 +91,11 @@ impl NewGameModal {
         let cell_width = self.game_square / 9.0;
         self.width = cell_width * 4.0;
         self.x = self.game_square / 2.0 - (self.width / 2.0);
-        self.height = cell_width * 1.5;
+        self.row_height = cell_width * 1.5;
+        self.height = self.row_height + (cell_width * 3.75);
         self.y = self.game_square / 2.0 - (self.height / 2.0);
 
-        let button_y = self.y + self.height / 2.0 - self.font_height / 2.0;
+        let button_y = self.y + self.row_height / 2.0 - self.font_height / 2.0;
 
         let button_2_x = (self.game_square / 2.0) - self.font_width;
         self.difficulty_2.update(button_2_x, button_y, cell_width);
@@ -74,6 +105,61 @@ impl NewGameModal {
 
         let button_3_x = button_2_x + (cell_width * 1.25);
         self.difficulty_3.update(button_3_x, button_y, cell_width);
+
+        self.seed.update(
+            self.x + (cell_width * 0.25),
+            self.y + self.row_height,
+            self.width - (cell_width * 0.5),
+            cell_width * 0.5,
+        );
+
+        self.symmetry_button.update(
+            self.x + (cell_width * 0.25),
+            self.y + self.row_height + (cell_width * 0.75),
+            self.width - (cell_width * 0.5),
+            cell_width * 0.5,
+        );
+
+        self.minimal_button.update(
+            self.x + (cell_width * 0.25),
+            self.y + self.row_height + (cell_width * 1.5),
+            self.width - (cell_width * 0.5),
+            cell_width * 0.5,
+        );
+
+        self.daily.update(
+            self.x + (cell_width * 0.25),
+            self.y + self.row_height + (cell_width * 2.25),
+            self.width - (cell_width * 0.5),
+            cell_width * 0.5,
+        );
+
+        self.import.update(
+            self.x + (cell_width * 0.25),
+            self.y + self.row_height + (cell_width * 3.0),
+            self.width - (cell_width * 0.5),
+            cell_width * 0.5,
+        );
+    }
+
+    // A typed full game key, which decides the options on its own.
+    pub fn typed_key(&self) -> Option<GameKey> {
+        GameKey::parse(&self.seed_input.text)
+    }
+
+    // The game to start for the picked difficulty, a bare seed takes the options
+    // shown below it. None starts a random game.
+    pub fn game_key(&self, difficulty: u8) -> Option<GameKey> {
+        if let Some(key) = self.typed_key() {
+            return Some(key);
+        }
+
+        self.seed_input.text.parse().ok().map(|seed| GameKey {
+            seed,
+            difficulty,
+            symmetry: self.symmetry,
+            minimal: self.minimal,
+        })
     }
 
     pub fn click(&mut self, x: f32, y: f32) -> Option<u8> {
@@ -0,0 +1,116 @@
+use crate::text_button::TextButton;
+
+pub struct OptionsModal {
+    pub show: bool,
+    pub x: f32,
+    pub y: f32,
+    pub game_square: f32,
+    pub width: f32,
+    pub height: f32,
+    pub title_height: f32,
+    pub message_y: f32,
+    pub free_entry: TextButton,
+    pub input_mode: TextButton,
+    pub mistake_limit: TextButton,
+    pub fill_candidates: TextButton,
+    pub auto_candidates: TextButton,
+    pub checkpoints: TextButton,
+    pub statistics: TextButton,
+    pub key_bindings: TextButton,
+    pub copy_line: TextButton,
+    pub copy_grid: TextButton,
+    pub copy_state: TextButton,
+    pub copied: bool,
+}
+
+impl Default for OptionsModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OptionsModal {
+    pub fn new() -> Self {
+        OptionsModal {
+            show: false,
+            x: 0.0,
+            y: 0.0,
+            game_square: 0.0,
+            width: 0.0,
+            height: 0.0,
+            title_height: 0.0,
+            message_y: 0.0,
+            free_entry: Default::default(),
+            input_mode: Default::default(),
+            mistake_limit: Default::default(),
+            fill_candidates: Default::default(),
+            auto_candidates: Default::default(),
+            checkpoints: Default::default(),
+            statistics: Default::default(),
+            key_bindings: Default::default(),
+            copy_line: Default::default(),
+            copy_grid: Default::default(),
+            copy_state: Default::default(),
+            copied: false,
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.show = true;
+        self.copied = false;
+
+        self.update_options();
+    }
+
+    pub fn hide(&mut self) {
+        self.show = false;
+    }
+
+    pub fn update(&mut self, game_square: f32) {
+        self.game_square = game_square;
+
+        if self.show {
+            self.update_options();
+        }
+    }
+
+    pub fn update_options(&mut self) {
+        let cell_width = self.game_square / 9.0;
+        self.width = cell_width * 5.0;
+        self.x = self.game_square / 2.0 - (self.width / 2.0);
+        self.title_height = cell_width * 0.75;
+        // a row for each button and the message under them
+        let row_height = cell_width * 0.65;
+        let rows_height = row_height * 11.0;
+        self.height = self.title_height + rows_height + (cell_width * 0.5);
+        self.y = self.game_square / 2.0 - (self.height / 2.0);
+
+        let buttons = [
+            &mut self.free_entry,
+            &mut self.input_mode,
+            &mut self.mistake_limit,
+            &mut self.fill_candidates,
+            &mut self.auto_candidates,
+            &mut self.checkpoints,
+            &mut self.statistics,
+            &mut self.key_bindings,
+            &mut self.copy_line,
+            &mut self.copy_grid,
+            &mut self.copy_state,
+        ];
+        for (i, button) in buttons.into_iter().enumerate() {
+            button.update(
+                self.x + (cell_width * 0.25),
+                self.y + self.title_height + (i as f32 * row_height),
+                self.width - (cell_width * 0.5),
+                cell_width * 0.5,
+            );
+        }
+
+        self.message_y = self.y + self.title_height + rows_height + (cell_width * 0.15);
+    }
+
+    pub fn click_outside(&mut self, x: f32, y: f32) -> bool {
+        x < self.x || x > self.x + self.width || y < self.y || y > self.y + self.height
+    }
+}
@@ -0,0 +1,198 @@
+use crate::menu::Menu;
+use crate::text_button::TextButton;
+
+// Seconds each move stays on the board while playing.
+const STEP_SECONDS: f32 = 0.6;
+
+// Steps through a finished game on the board, shown in place of the menu.
+pub struct Replay {
+    // The history nodes of every move in the order they were made.
+    pub moves: Vec<usize>,
+    // How many of the moves are on the board.
+    pub step: usize,
+    pub playing: bool,
+    pub since_step: f32,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub info_height: f32,
+    pub track: TextButton,
+    pub start: TextButton,
+    pub back: TextButton,
+    pub play: TextButton,
+    pub next: TextButton,
+    pub end: TextButton,
+    pub close: TextButton,
+}
+
+impl Replay {
+    pub fn new(moves: Vec<usize>) -> Self {
+        Replay {
+            moves,
+            step: 0,
+            playing: true,
+            since_step: 0.0,
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            info_height: 0.0,
+            track: Default::default(),
+            start: Default::default(),
+            back: Default::default(),
+            play: Default::default(),
+            next: Default::default(),
+            end: Default::default(),
+            close: Default::default(),
+        }
+    }
+
+    // The history node for the board at the current step, None for the start.
+    pub fn target(&self) -> Option<usize> {
+        self.step.checked_sub(1).map(|i| self.moves[i])
+    }
+
+    pub fn set_step(&mut self, step: usize) {
+        self.step = step.min(self.moves.len());
+        self.since_step = 0.0;
+    }
+
+    pub fn toggle_play(&mut self) {
+        // playing from the end starts over
+        if !self.playing && self.step == self.moves.len() {
+            self.set_step(0);
+        }
+        self.playing = !self.playing;
+        self.since_step = 0.0;
+    }
+
+    // Returns true if it moved on to the next step.
+    pub fn advance(&mut self, seconds: f32) -> bool {
+        if !self.playing {
+            return false;
+        }
+
+        self.since_step += seconds;
+        if self.since_step < STEP_SECONDS {
+            return false;
+        }
+
+        self.set_step(self.step + 1);
+        if self.step == self.moves.len() {
+            self.playing = false;
+        }
+        true
+    }
+
+    // The step for a click on the track, which runs from the start to the end.
+    pub fn scrub(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.track.click(x, y) || self.track.width <= 0.0 {
+            return None;
+        }
+
+        let fraction = (x - self.track.x) / self.track.width;
+        Some((fraction * self.moves.len() as f32).round() as usize)
+    }
+
+    pub fn update(&mut self, menu: &Menu) {
+        let item_size = menu.item_size;
+        self.x = menu.menu_start_x;
+        self.y = menu.menu_start_y;
+        self.width = if menu.portrait {
+            menu.board_size
+        } else {
+            item_size * 2.5
+        };
+        self.info_height = item_size * 0.5;
+
+        self.track.update(
+            self.x,
+            self.y + (item_size * 0.6),
+            self.width,
+            item_size * 0.35,
+        );
+
+        // a row of buttons under the board, a column next to it
+        let buttons_y = self.y + (item_size * 1.2);
+        let button_height = item_size * 0.6;
+        let gap = item_size * 0.15;
+        let buttons = [
+            &mut self.start,
+            &mut self.back,
+            &mut self.play,
+            &mut self.next,
+            &mut self.end,
+            &mut self.close,
+        ];
+        let count = buttons.len() as f32;
+        for (i, button) in buttons.into_iter().enumerate() {
+            if menu.portrait {
+                let button_width = (self.width - (gap * (count - 1.0))) / count;
+                button.update(
+                    self.x + (i as f32 * (button_width + gap)),
+                    buttons_y,
+                    button_width,
+                    button_height,
+                );
+            } else {
+                button.update(
+                    self.x,
+                    buttons_y + (i as f32 * (button_height + gap)),
+                    self.width,
+                    button_height,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Replay, STEP_SECONDS};
+
+    #[test]
+    fn test_advance() {
+        let mut replay = Replay::new(vec![0, 2, 3]);
+        assert_eq!(replay.target(), None);
+
+        assert!(!replay.advance(STEP_SECONDS / 2.0));
+        assert!(replay.advance(STEP_SECONDS / 2.0));
+        assert_eq!(replay.target(), Some(0));
+
+        assert!(replay.advance(STEP_SECONDS));
+        assert!(replay.advance(STEP_SECONDS));
+        assert_eq!(replay.target(), Some(3));
+
+        // stops at the end
+        assert!(!replay.playing);
+        assert!(!replay.advance(STEP_SECONDS));
+
+        // and starts over from there
+        replay.toggle_play();
+        assert!(replay.playing);
+        assert_eq!(replay.step, 0);
+    }
+
+    #[test]
+    fn test_set_step() {
+        let mut replay = Replay::new(vec![0, 1]);
+        replay.set_step(5);
+        assert_eq!(replay.step, 2);
+
+        replay.toggle_play();
+        assert!(!replay.playing);
+        replay.set_step(1);
+        assert!(!replay.advance(STEP_SECONDS));
+        assert_eq!(replay.target(), Some(0));
+    }
+
+    #[test]
+    fn test_scrub() {
+        let mut replay = Replay::new(vec![0, 1, 2, 3]);
+        replay.track.update(100.0, 10.0, 200.0, 10.0);
+
+        assert_eq!(replay.scrub(100.0, 15.0), Some(0));
+        assert_eq!(replay.scrub(200.0, 15.0), Some(2));
+        assert_eq!(replay.scrub(300.0, 15.0), Some(4));
+        assert_eq!(replay.scrub(200.0, 30.0), None);
+    }
+}
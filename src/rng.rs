@@ -0,0 +1,84 @@
+// Small seedable random number generator (SplitMix64), kept separate from
+// macroquad's global generator so the same seed gives the same puzzle everywhere.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Returns a number in [low, high).
+    pub fn gen_range(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next_u64() % (high - low) as u64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, array: &mut [T]) {
+        let mut i = array.len();
+        while i >= 2 {
+            i -= 1;
+            let j = self.gen_range(0, i + 1);
+            array.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(1234);
+        let mut b = Rng::new(1234);
+        let mut c = Rng::new(1235);
+
+        let a_values: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        let b_values: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+        let c_values: Vec<u64> = (0..10).map(|_| c.next_u64()).collect();
+
+        assert_eq!(a_values, b_values);
+        assert_ne!(a_values, c_values);
+    }
+
+    #[test]
+    fn known_values() {
+        // reference values for SplitMix64 seeded with 0
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    }
+
+    #[test]
+    fn range() {
+        let mut rng = Rng::new(42);
+        for _ in 0..1000 {
+            let value = rng.gen_range(3, 9);
+            assert!((3..9).contains(&value));
+        }
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut rng = Rng::new(7);
+        let mut values = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        rng.shuffle(&mut values);
+
+        let mut sorted = values;
+        sorted.sort();
+        assert_eq!(sorted, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let mut again = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        Rng::new(7).shuffle(&mut again);
+        assert_eq!(values, again);
+    }
+}
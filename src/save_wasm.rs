@@ -1,3 +1,5 @@
+use crate::statistics::GameRecord;
+
 pub fn load(key: &str) -> Option<String> {
     let storage = &mut quad_storage::STORAGE.lock().unwrap();
     storage.get(key)
@@ -7,3 +9,21 @@ pub fn save(key: &str, value: &str) {
     let storage = &mut quad_storage::STORAGE.lock().unwrap();
     storage.set(key, value);
 }
+
+// All the games are kept as one JSON list.
+pub fn load_games() -> Vec<GameRecord> {
+    load("games")
+        .and_then(|games_str| serde_json::from_str(&games_str).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_game(game: &GameRecord) {
+    let mut games = load_games();
+    match games.iter_mut().find(|saved| saved.id == game.id) {
+        Some(saved) => *saved = *game,
+        None => games.push(*game),
+    }
+
+    let save_data = serde_json::to_string(&games).unwrap();
+    save("games", save_data.as_str());
+}
@@ -1,5 +1,7 @@
 use rusqlite::{params, Connection, OpenFlags};
 
+use crate::statistics::{GameOutcome, GameRecord};
+
 pub fn load(key: &str) -> Option<String> {
     let db = get_connection();
     let mut stmt = db.prepare("SELECT value FROM save WHERE key = ?1").unwrap();
@@ -24,11 +26,62 @@ pub fn save(key: &str, value: &str) {
     .unwrap();
 }
 
+pub fn load_games() -> Vec<GameRecord> {
+    let db = get_connection();
+    let mut stmt = db
+        .prepare(
+            "SELECT id, difficulty, outcome, seconds, mistakes, hints, day FROM games ORDER BY id",
+        )
+        .unwrap();
+
+    let rows = stmt.query_map(params![], |row| {
+        let outcome: String = row.get(2)?;
+        Ok(GameRecord {
+            id: row.get::<_, i64>(0)? as u64,
+            difficulty: row.get(1)?,
+            outcome: GameOutcome::from_name(&outcome).unwrap_or(GameOutcome::Abandoned),
+            seconds: row.get(3)?,
+            mistakes: row.get(4)?,
+            hints: row.get(5)?,
+            day: row.get(6)?,
+        })
+    });
+
+    match rows {
+        Ok(rows) => rows.filter_map(|row| row.ok()).collect(),
+        Err(_) => vec![],
+    }
+}
+
+pub fn save_game(game: &GameRecord) {
+    let db = get_connection();
+    db.execute(
+        "REPLACE INTO games (id, difficulty, outcome, seconds, mistakes, hints, day) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            game.id as i64,
+            game.difficulty,
+            game.outcome.name(),
+            game.seconds,
+            game.mistakes,
+            game.hints,
+            game.day
+        ],
+    )
+    .unwrap();
+}
+
 fn migrate_db(db: &Connection) {
     let mut stmt = db
         .prepare("CREATE TABLE IF NOT EXISTS save (key VARCHAR(255) PRIMARY KEY, value LONGTEXT);")
         .unwrap();
     stmt.execute(params![]).unwrap();
+
+    let mut stmt = db
+        .prepare(
+            "CREATE TABLE IF NOT EXISTS games (id INTEGER PRIMARY KEY, difficulty INTEGER, outcome VARCHAR(16), seconds REAL, mistakes INTEGER, hints INTEGER, day INTEGER);",
+        )
+        .unwrap();
+    stmt.execute(params![]).unwrap();
 }
 
 fn get_connection() -> Connection {
@@ -0,0 +1,67 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::save::{load, save};
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Settings {
+    // Wrong numbers are kept and their conflicts shown instead of
+    // the placement being undone.
+    #[serde(default)]
+    pub free_entry: bool,
+    // The game is lost after this many mistakes, no limit if None.
+    #[serde(default)]
+    pub mistake_limit: Option<u32>,
+    // Pencil marks are filled in for new games and put back when a
+    // number is removed.
+    #[serde(default)]
+    pub auto_candidates: bool,
+    // A cell is picked first and a number put in it, instead of picking a
+    // number and then the cells for it.
+    #[serde(default)]
+    pub cell_first: bool,
+}
+
+const MISTAKE_LIMITS: [Option<u32>; 3] = [None, Some(3), Some(5)];
+
+impl Settings {
+    pub fn load() -> Self {
+        load("settings")
+            .and_then(|settings_str| serde_json::from_str(&settings_str).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let save_data = serde_json::to_string(self).unwrap();
+        save("settings", save_data.as_str());
+    }
+
+    pub fn next_mistake_limit(&mut self) {
+        let current = MISTAKE_LIMITS
+            .iter()
+            .position(|limit| *limit == self.mistake_limit)
+            .unwrap_or(0);
+        self.mistake_limit = MISTAKE_LIMITS[(current + 1) % MISTAKE_LIMITS.len()];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+
+    #[test]
+    fn mistake_limit() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.mistake_limit, None);
+
+        settings.next_mistake_limit();
+        assert_eq!(settings.mistake_limit, Some(3));
+
+        settings.next_mistake_limit();
+        settings.next_mistake_limit();
+        assert_eq!(settings.mistake_limit, None);
+
+        settings.mistake_limit = Some(4);
+        settings.next_mistake_limit();
+        assert_eq!(settings.mistake_limit, Some(3));
+    }
+}
@@ -0,0 +1,296 @@
+// Constraint solver working on candidate bitmasks.
+//
+// Every row, column and box keeps a 9 bit mask of the digits already used in it,
+// bit 0 being the digit 1. The candidates of a cell are whatever is not used by
+// any of its three houses, so checking a value is a couple of bit operations
+// instead of a scan. The search always branches on the empty cell with the fewest
+// candidates, which keeps the backtracking tree small.
+
+use crate::logic::house::box_of;
+use crate::rng::Rng;
+
+const ALL_CANDIDATES: u16 = 0x1FF;
+
+pub fn digit_mask(value: u8) -> u16 {
+    1 << (value - 1)
+}
+
+struct Solver {
+    board: [[u8; 9]; 9],
+    rows: [u16; 9],
+    cols: [u16; 9],
+    boxes: [u16; 9],
+    // Tries the candidates in a random order instead of from the lowest.
+    rng: Option<Rng>,
+}
+
+impl Solver {
+    // Returns None if the given numbers already clash with each other.
+    fn new(board: &[[u8; 9]; 9]) -> Option<Self> {
+        let mut solver = Solver {
+            board: [[0; 9]; 9],
+            rows: [0; 9],
+            cols: [0; 9],
+            boxes: [0; 9],
+            rng: None,
+        };
+
+        for (row, row_values) in board.iter().enumerate() {
+            for (col, &value) in row_values.iter().enumerate() {
+                if value == 0 {
+                    continue;
+                }
+
+                if value > 9 || solver.candidates(row, col) & digit_mask(value) == 0 {
+                    return None;
+                }
+
+                solver.place(row, col, value);
+            }
+        }
+
+        Some(solver)
+    }
+
+    fn candidates(&self, row: usize, col: usize) -> u16 {
+        !(self.rows[row] | self.cols[col] | self.boxes[box_of(row * 9 + col)]) & ALL_CANDIDATES
+    }
+
+    fn place(&mut self, row: usize, col: usize, value: u8) {
+        let mask = digit_mask(value);
+        self.board[row][col] = value;
+        self.rows[row] |= mask;
+        self.cols[col] |= mask;
+        self.boxes[box_of(row * 9 + col)] |= mask;
+    }
+
+    fn remove(&mut self, row: usize, col: usize) {
+        let mask = !digit_mask(self.board[row][col]);
+        self.board[row][col] = 0;
+        self.rows[row] &= mask;
+        self.cols[col] &= mask;
+        self.boxes[box_of(row * 9 + col)] &= mask;
+    }
+
+    // Finds the empty cell with the fewest candidates, stopping early
+    // if a cell has none (dead end) or a single one (forced).
+    fn most_constrained_cell(&self) -> Option<(usize, usize, u16)> {
+        let mut best: Option<(usize, usize, u16)> = None;
+        let mut best_count = u32::MAX;
+
+        for row in 0..9 {
+            for col in 0..9 {
+                if self.board[row][col] != 0 {
+                    continue;
+                }
+
+                let candidates = self.candidates(row, col);
+                let count = candidates.count_ones();
+                if count < best_count {
+                    best = Some((row, col, candidates));
+                    best_count = count;
+
+                    if count <= 1 {
+                        return best;
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    // Counts solutions up to `limit`, leaving the first one found in `solution`.
+    fn search(&mut self, limit: usize, count: &mut usize, solution: &mut Option<[[u8; 9]; 9]>) {
+        let Some((row, col, mut candidates)) = self.most_constrained_cell() else {
+            *count += 1;
+            if solution.is_none() {
+                *solution = Some(self.board);
+            }
+            return;
+        };
+
+        let mut values = [0; 9];
+        let mut len = 0;
+        while candidates != 0 {
+            values[len] = candidates.trailing_zeros() as u8 + 1;
+            candidates &= candidates - 1;
+            len += 1;
+        }
+        if let Some(rng) = &mut self.rng {
+            rng.shuffle(&mut values[..len]);
+        }
+
+        for &value in &values[..len] {
+            self.place(row, col, value);
+            self.search(limit, count, solution);
+            self.remove(row, col);
+
+            if *count >= limit {
+                return;
+            }
+        }
+    }
+}
+
+pub fn solve(board: &mut [[u8; 9]; 9]) -> bool {
+    let Some(mut solver) = Solver::new(board) else {
+        return false;
+    };
+
+    let mut count = 0;
+    let mut solution = None;
+    solver.search(1, &mut count, &mut solution);
+
+    if let Some(solution) = solution {
+        *board = solution;
+        return true;
+    }
+
+    false
+}
+
+// Like solve, but the candidates are tried in an order picked by the rng, so
+// the same givens can be finished in many different ways.
+pub fn fill(board: &mut [[u8; 9]; 9], rng: &mut Rng) -> bool {
+    let Some(mut solver) = Solver::new(board) else {
+        return false;
+    };
+    solver.rng = Some(Rng::new(rng.next_u64()));
+
+    let mut count = 0;
+    let mut solution = None;
+    solver.search(1, &mut count, &mut solution);
+
+    if let Some(solution) = solution {
+        *board = solution;
+        return true;
+    }
+
+    false
+}
+
+pub fn count_solutions(board: &[[u8; 9]; 9], limit: usize) -> usize {
+    if limit == 0 {
+        return 0;
+    }
+
+    let Some(mut solver) = Solver::new(board) else {
+        return 0;
+    };
+
+    let mut count = 0;
+    let mut solution = None;
+    solver.search(limit, &mut count, &mut solution);
+    count
+}
+
+pub fn is_unique(board: &[[u8; 9]; 9]) -> bool {
+    count_solutions(board, 2) == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: [[u8; 9]; 9] = [
+        [5, 3, 0, 0, 7, 0, 0, 0, 0],
+        [6, 0, 0, 1, 9, 5, 0, 0, 0],
+        [0, 9, 8, 0, 0, 0, 0, 6, 0],
+        [8, 0, 0, 0, 6, 0, 0, 0, 3],
+        [4, 0, 0, 8, 0, 3, 0, 0, 1],
+        [7, 0, 0, 0, 2, 0, 0, 0, 6],
+        [0, 6, 0, 0, 0, 0, 2, 8, 0],
+        [0, 0, 0, 4, 1, 9, 0, 0, 5],
+        [0, 0, 0, 0, 8, 0, 0, 7, 9],
+    ];
+
+    const SOLUTION: [[u8; 9]; 9] = [
+        [5, 3, 4, 6, 7, 8, 9, 1, 2],
+        [6, 7, 2, 1, 9, 5, 3, 4, 8],
+        [1, 9, 8, 3, 4, 2, 5, 6, 7],
+        [8, 5, 9, 7, 6, 1, 4, 2, 3],
+        [4, 2, 6, 8, 5, 3, 7, 9, 1],
+        [7, 1, 3, 9, 2, 4, 8, 5, 6],
+        [9, 6, 1, 5, 3, 7, 2, 8, 4],
+        [2, 8, 7, 4, 1, 9, 6, 3, 5],
+        [3, 4, 5, 2, 8, 6, 1, 7, 9],
+    ];
+
+    #[test]
+    fn solve_puzzle() {
+        let mut board = PUZZLE;
+        assert!(solve(&mut board));
+        assert_eq!(board, SOLUTION);
+    }
+
+    #[test]
+    fn solve_empty() {
+        let mut board = [[0; 9]; 9];
+        assert!(solve(&mut board));
+        assert_eq!(count_solutions(&board, 2), 1);
+    }
+
+    #[test]
+    fn solve_conflict() {
+        let mut board = PUZZLE;
+        board[0][2] = 5;
+        assert!(!solve(&mut board));
+        assert_eq!(count_solutions(&board, 2), 0);
+
+        let mut board = PUZZLE;
+        board[0][2] = 10;
+        assert!(!solve(&mut board));
+    }
+
+    #[test]
+    fn solve_dead_end() {
+        // the top left cell has no candidates left, but no givens clash
+        let mut board = [[0; 9]; 9];
+        board[0] = [0, 1, 2, 3, 4, 5, 6, 7, 0];
+        board[1][0] = 8;
+        board[2][8] = 9;
+        board[3][0] = 9;
+        assert!(!solve(&mut board));
+        assert_eq!(count_solutions(&board, 2), 0);
+    }
+
+    #[test]
+    fn fill_varies() {
+        let mut rng = Rng::new(3);
+        let mut first = [[0; 9]; 9];
+        assert!(fill(&mut first, &mut rng));
+        assert!(is_unique(&first));
+
+        // the same diagonal boxes still lead to different grids
+        let mut second = [[0; 9]; 9];
+        for i in (0..9).step_by(3) {
+            for row in i..i + 3 {
+                second[row][i..i + 3].copy_from_slice(&first[row][i..i + 3]);
+            }
+        }
+        let mut third = second;
+        assert!(fill(&mut second, &mut rng));
+        assert!(fill(&mut third, &mut rng));
+        assert_ne!(second, third);
+
+        let mut again = [[0; 9]; 9];
+        fill(&mut again, &mut Rng::new(3));
+        assert_eq!(again, first);
+    }
+
+    #[test]
+    fn count_limit() {
+        let board = [[0; 9]; 9];
+        assert_eq!(count_solutions(&board, 0), 0);
+        assert_eq!(count_solutions(&board, 1), 1);
+        assert_eq!(count_solutions(&board, 5), 5);
+        assert!(!is_unique(&board));
+    }
+
+    #[test]
+    fn unique_puzzle() {
+        assert!(is_unique(&PUZZLE));
+        assert!(is_unique(&SOLUTION));
+    }
+}
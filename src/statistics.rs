@@ -0,0 +1,229 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::save::{load_games, save_game};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum GameOutcome {
+    Playing,
+    Won,
+    Lost,
+    // Replaced by a new game before it was over.
+    Abandoned,
+}
+
+impl GameOutcome {
+    pub fn name(self) -> &'static str {
+        match self {
+            GameOutcome::Playing => "playing",
+            GameOutcome::Won => "won",
+            GameOutcome::Lost => "lost",
+            GameOutcome::Abandoned => "abandoned",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "playing" => Some(GameOutcome::Playing),
+            "won" => Some(GameOutcome::Won),
+            "lost" => Some(GameOutcome::Lost),
+            "abandoned" => Some(GameOutcome::Abandoned),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GameRecord {
+    pub id: u64,
+    pub difficulty: u8,
+    pub outcome: GameOutcome,
+    pub seconds: f64,
+    pub mistakes: u32,
+    pub hints: u32,
+    // The day the game was started, see daily::today.
+    pub day: u32,
+}
+
+// Only games that are over count, the one being played doesn't yet.
+#[derive(Debug, Default, PartialEq)]
+pub struct DifficultyStats {
+    pub played: u32,
+    pub won: u32,
+    pub best_seconds: Option<f64>,
+    pub average_seconds: Option<f64>,
+    pub streak: u32,
+    pub best_streak: u32,
+}
+
+impl DifficultyStats {
+    // As a percentage.
+    pub fn win_rate(&self) -> Option<f64> {
+        if self.played == 0 {
+            return None;
+        }
+
+        Some(self.won as f64 * 100.0 / self.played as f64)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Statistics {
+    pub games: Vec<GameRecord>,
+}
+
+impl Statistics {
+    pub fn load() -> Self {
+        Statistics {
+            games: load_games(),
+        }
+    }
+
+    pub fn save_game(&self, id: u64) {
+        if let Some(game) = self.games.iter().find(|game| game.id == id) {
+            save_game(game);
+        }
+    }
+
+    // Records a new game as being played and returns its id.
+    pub fn start(&mut self, difficulty: u8, day: u32) -> u64 {
+        let id = self.games.iter().map(|game| game.id + 1).max().unwrap_or(1);
+        self.games.push(GameRecord {
+            id,
+            difficulty,
+            outcome: GameOutcome::Playing,
+            seconds: 0.0,
+            mistakes: 0,
+            hints: 0,
+            day,
+        });
+
+        id
+    }
+
+    // Returns false if there's no such game or it's already over.
+    pub fn finish(
+        &mut self,
+        id: u64,
+        outcome: GameOutcome,
+        seconds: f64,
+        mistakes: u32,
+        hints: u32,
+    ) -> bool {
+        let Some(game) = self
+            .games
+            .iter_mut()
+            .find(|game| game.id == id && game.outcome == GameOutcome::Playing)
+        else {
+            return false;
+        };
+
+        game.outcome = outcome;
+        game.seconds = seconds;
+        game.mistakes = mistakes;
+        game.hints = hints;
+        true
+    }
+
+    pub fn difficulty_stats(&self, difficulty: u8) -> DifficultyStats {
+        let mut stats = DifficultyStats::default();
+        let mut total_seconds = 0.0;
+
+        // games are kept in the order they were started
+        let games = self
+            .games
+            .iter()
+            .filter(|game| game.difficulty == difficulty && game.outcome != GameOutcome::Playing);
+        for game in games {
+            stats.played += 1;
+            if game.outcome != GameOutcome::Won {
+                stats.streak = 0;
+                continue;
+            }
+
+            stats.won += 1;
+            stats.streak += 1;
+            stats.best_streak = stats.best_streak.max(stats.streak);
+            total_seconds += game.seconds;
+            stats.best_seconds = Some(
+                stats
+                    .best_seconds
+                    .map_or(game.seconds, |best| best.min(game.seconds)),
+            );
+        }
+
+        if stats.won > 0 {
+            stats.average_seconds = Some(total_seconds / stats.won as f64);
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameOutcome, Statistics};
+
+    fn play(statistics: &mut Statistics, difficulty: u8, outcome: GameOutcome, seconds: f64) {
+        let id = statistics.start(difficulty, 100);
+        assert!(statistics.finish(id, outcome, seconds, 1, 0));
+    }
+
+    #[test]
+    fn test_start_and_finish() {
+        let mut statistics = Statistics::default();
+        let first = statistics.start(1, 100);
+        let second = statistics.start(2, 101);
+        assert_ne!(first, second);
+
+        assert!(statistics.finish(first, GameOutcome::Won, 60.0, 2, 1));
+        assert!(!statistics.finish(first, GameOutcome::Lost, 90.0, 3, 1));
+        assert!(!statistics.finish(42, GameOutcome::Won, 60.0, 0, 0));
+
+        let game = statistics.games[0];
+        assert_eq!(game.outcome, GameOutcome::Won);
+        assert_eq!(game.seconds, 60.0);
+        assert_eq!(game.mistakes, 2);
+        assert_eq!(game.hints, 1);
+        assert_eq!(statistics.games[1].outcome, GameOutcome::Playing);
+    }
+
+    #[test]
+    fn test_difficulty_stats() {
+        let mut statistics = Statistics::default();
+        assert_eq!(statistics.difficulty_stats(1).win_rate(), None);
+
+        play(&mut statistics, 1, GameOutcome::Won, 100.0);
+        play(&mut statistics, 1, GameOutcome::Won, 200.0);
+        play(&mut statistics, 2, GameOutcome::Lost, 50.0);
+        play(&mut statistics, 1, GameOutcome::Abandoned, 10.0);
+        play(&mut statistics, 1, GameOutcome::Won, 300.0);
+        statistics.start(1, 100);
+
+        let stats = statistics.difficulty_stats(1);
+        assert_eq!(stats.played, 4);
+        assert_eq!(stats.won, 3);
+        assert_eq!(stats.win_rate(), Some(75.0));
+        assert_eq!(stats.best_seconds, Some(100.0));
+        assert_eq!(stats.average_seconds, Some(200.0));
+        assert_eq!(stats.streak, 1);
+        assert_eq!(stats.best_streak, 2);
+
+        let stats = statistics.difficulty_stats(2);
+        assert_eq!(stats.played, 1);
+        assert_eq!(stats.best_seconds, None);
+        assert_eq!(stats.streak, 0);
+    }
+
+    #[test]
+    fn test_outcome_names() {
+        for outcome in [
+            GameOutcome::Playing,
+            GameOutcome::Won,
+            GameOutcome::Lost,
+            GameOutcome::Abandoned,
+        ] {
+            assert_eq!(GameOutcome::from_name(outcome.name()), Some(outcome));
+        }
+        assert_eq!(GameOutcome::from_name("unknown"), None);
+    }
+}
@@ -0,0 +1,65 @@
+pub struct StatisticsModal {
+    pub show: bool,
+    pub x: f32,
+    pub y: f32,
+    pub game_square: f32,
+    pub width: f32,
+    pub height: f32,
+    pub title_height: f32,
+    pub row_height: f32,
+}
+
+impl Default for StatisticsModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatisticsModal {
+    pub fn new() -> Self {
+        StatisticsModal {
+            show: false,
+            x: 0.0,
+            y: 0.0,
+            game_square: 0.0,
+            width: 0.0,
+            height: 0.0,
+            title_height: 0.0,
+            row_height: 0.0,
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.show = true;
+
+        self.update_statistics();
+    }
+
+    pub fn hide(&mut self) {
+        self.show = false;
+    }
+
+    pub fn update(&mut self, game_square: f32) {
+        self.game_square = game_square;
+
+        if self.show {
+            self.update_statistics();
+        }
+    }
+
+    pub fn update_statistics(&mut self) {
+        let cell_width = self.game_square / 9.0;
+        self.width = cell_width * 8.0;
+        self.x = self.game_square / 2.0 - (self.width / 2.0);
+        self.title_height = cell_width * 0.75;
+        self.row_height = cell_width * 0.5;
+
+        // a header row and a row for each difficulty
+        self.height = self.title_height + (self.row_height * 4.0) + (cell_width * 0.25);
+        self.y = self.game_square / 2.0 - (self.height / 2.0);
+    }
+
+    pub fn click_outside(&mut self, x: f32, y: f32) -> bool {
+        x < self.x || x > self.x + self.width || y < self.y || y > self.y + self.height
+    }
+}
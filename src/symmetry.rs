@@ -0,0 +1,132 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::index::{index_to_xy, xy_to_index};
+
+// How clues are removed from a puzzle, every cell is removed together
+// with its mirror image so the givens form a symmetric pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Symmetry {
+    #[default]
+    None,
+    Rotational,
+    MirrorHorizontal,
+    MirrorVertical,
+    Diagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 5] = [
+        Symmetry::None,
+        Symmetry::Rotational,
+        Symmetry::MirrorHorizontal,
+        Symmetry::MirrorVertical,
+        Symmetry::Diagonal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Symmetry::None => "none",
+            Symmetry::Rotational => "rotational",
+            Symmetry::MirrorHorizontal => "horizontal",
+            Symmetry::MirrorVertical => "vertical",
+            Symmetry::Diagonal => "diagonal",
+        }
+    }
+
+    pub fn next(&self) -> Symmetry {
+        match self {
+            Symmetry::None => Symmetry::Rotational,
+            Symmetry::Rotational => Symmetry::MirrorHorizontal,
+            Symmetry::MirrorHorizontal => Symmetry::MirrorVertical,
+            Symmetry::MirrorVertical => Symmetry::Diagonal,
+            Symmetry::Diagonal => Symmetry::None,
+        }
+    }
+
+    // The digit the symmetry is written as in a game key.
+    pub fn number(&self) -> u8 {
+        Symmetry::ALL
+            .iter()
+            .position(|symmetry| symmetry == self)
+            .unwrap() as u8
+    }
+
+    pub fn from_number(number: u8) -> Option<Symmetry> {
+        Symmetry::ALL.get(number as usize).copied()
+    }
+
+    pub fn partner(&self, index: usize) -> usize {
+        let (x, y) = index_to_xy(index, 9);
+        let (x, y) = match self {
+            Symmetry::None => (x, y),
+            Symmetry::Rotational => (8 - x, 8 - y),
+            Symmetry::MirrorHorizontal => (x, 8 - y),
+            Symmetry::MirrorVertical => (8 - x, y),
+            Symmetry::Diagonal => (y, x),
+        };
+        xy_to_index(x, y, 9)
+    }
+
+    // The cell and its partner, just the cell if it lies on the axis.
+    pub fn group(&self, index: usize) -> Vec<usize> {
+        let partner = self.partner(index);
+        if partner == index {
+            vec![index]
+        } else {
+            vec![index, partner]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Symmetry;
+
+    #[test]
+    fn partners() {
+        assert_eq!(Symmetry::None.partner(10), 10);
+        assert_eq!(Symmetry::Rotational.partner(0), 80);
+        assert_eq!(Symmetry::Rotational.partner(40), 40);
+        assert_eq!(Symmetry::MirrorHorizontal.partner(1), 73);
+        assert_eq!(Symmetry::MirrorVertical.partner(9), 17);
+        assert_eq!(Symmetry::Diagonal.partner(1), 9);
+        assert_eq!(Symmetry::Diagonal.partner(20), 20);
+    }
+
+    #[test]
+    fn partner_of_partner() {
+        for symmetry in Symmetry::ALL {
+            for index in 0..81 {
+                assert_eq!(symmetry.partner(symmetry.partner(index)), index);
+            }
+        }
+    }
+
+    #[test]
+    fn groups() {
+        assert_eq!(Symmetry::None.group(5), vec![5]);
+        assert_eq!(Symmetry::Rotational.group(5), vec![5, 75]);
+        assert_eq!(Symmetry::Rotational.group(40), vec![40]);
+        assert_eq!(Symmetry::MirrorVertical.group(4), vec![4]);
+    }
+
+    #[test]
+    fn cycle() {
+        for symmetry in Symmetry::ALL {
+            let mut next = symmetry.next();
+            for _ in 0..4 {
+                assert_ne!(next, symmetry);
+                next = next.next();
+            }
+            assert_eq!(next, symmetry);
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        for symmetry in Symmetry::ALL {
+            assert_eq!(Symmetry::from_number(symmetry.number()), Some(symmetry));
+        }
+        assert_eq!(Symmetry::from_number(5), None);
+    }
+}
@@ -0,0 +1,54 @@
+#[derive(Clone, Copy)]
+pub struct TextButton {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for TextButton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextButton {
+    pub fn new() -> Self {
+        TextButton {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.x = x;
+        self.y = y;
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn click(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextButton;
+
+    #[test]
+    fn text_button_click() {
+        let mut button = TextButton::new();
+        assert_eq!(button.width, 0.0);
+        assert!(!button.click(1.0, 1.0));
+
+        button.update(10.0, 20.0, 30.0, 5.0);
+        assert!(!button.click(0.0, 0.0));
+        assert!(button.click(10.0, 20.0));
+        assert!(button.click(40.0, 25.0));
+        assert!(!button.click(40.0, 25.1));
+        assert!(!button.click(40.1, 25.0));
+    }
+}
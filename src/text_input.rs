@@ -0,0 +1,91 @@
+use macroquad::prelude::*;
+
+use crate::clipboard::get_clipboard;
+
+pub struct TextInput {
+    pub text: String,
+    pub max_length: usize,
+    pub accepts: fn(char) -> bool,
+}
+
+impl TextInput {
+    pub fn new(max_length: usize, accepts: fn(char) -> bool) -> Self {
+        TextInput {
+            text: String::new(),
+            max_length,
+            accepts,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn insert(&mut self, character: char) {
+        if self.text.chars().count() < self.max_length && (self.accepts)(character) {
+            self.text.push(character);
+        }
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        for character in text.chars() {
+            self.insert(character);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.text.pop();
+    }
+
+    // Typed characters, backspace and ctrl+v from the clipboard.
+    pub fn handle_input(&mut self) {
+        let ctrl = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+
+        while let Some(character) = get_char_pressed() {
+            if !ctrl {
+                self.insert(character);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            self.backspace();
+        }
+
+        if ctrl && is_key_pressed(KeyCode::V) {
+            if let Some(text) = get_clipboard() {
+                self.paste(&text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+
+    #[test]
+    fn insert_filters() {
+        let mut input = TextInput::new(4, |c| c.is_ascii_digit());
+        assert!(input.is_empty());
+
+        input.insert('1');
+        input.insert('a');
+        input.insert('2');
+        assert_eq!(input.text, "12");
+
+        input.paste("3 4 5 6");
+        assert_eq!(input.text, "1234");
+
+        input.backspace();
+        assert_eq!(input.text, "123");
+
+        input.clear();
+        assert!(input.is_empty());
+        input.backspace();
+        assert!(input.is_empty());
+    }
+}
@@ -1,17 +1,19 @@
-use crate::menu_item::MenuItem;
+use crate::text_button::TextButton;
 
 pub struct VictoryModal {
     pub show: bool,
     pub x: f32,
     pub y: f32,
     pub game_square: f32,
-    pub font_height: f32,
-    pub font_width: f32,
     pub width: f32,
     pub height: f32,
-    pub heart: MenuItem,
-    pub star_1: MenuItem,
-    pub star_2: MenuItem,
+    pub title_height: f32,
+    pub line_height: f32,
+    // Faster than any earlier win at the same difficulty.
+    pub personal_best: bool,
+    pub same_difficulty: TextButton,
+    pub choose: TextButton,
+    pub replay: TextButton,
 }
 
 impl Default for VictoryModal {
@@ -27,18 +29,20 @@ impl VictoryModal {
             x: 0.0,
             y: 0.0,
             game_square: 0.0,
-            font_height: 0.0,
-            font_width: 0.0,
             width: 0.0,
             height: 0.0,
-            heart: Default::default(),
-            star_1: Default::default(),
-            star_2: Default::default(),
+            title_height: 0.0,
+            line_height: 0.0,
+            personal_best: false,
+            same_difficulty: Default::default(),
+            choose: Default::default(),
+            replay: Default::default(),
         }
     }
 
-    pub fn show(&mut self) {
+    pub fn show(&mut self, personal_best: bool) {
         self.show = true;
+        self.personal_best = personal_best;
 
         self.update_victory();
     }
@@ -47,10 +51,8 @@ impl VictoryModal {
         self.show = false;
     }
 
-    pub fn update(&mut self, game_square: f32, font_width: f32, font_height: f32) {
+    pub fn update(&mut self, game_square: f32) {
         self.game_square = game_square;
-        self.font_width = font_width;
-        self.font_height = font_height;
 
         if self.show {
             self.update_victory();
@@ -59,21 +61,29 @@ impl VictoryModal {
 
     pub fn update_victory(&mut self) {
         let cell_width = self.game_square / 9.0;
-        self.width = cell_width * 4.0;
+        self.width = cell_width * 5.0;
         self.x = self.game_square / 2.0 - (self.width / 2.0);
-        self.height = cell_width * 1.5;
-        self.y = self.game_square / 2.0 - (self.height / 2.0);
-
-        let button_y = self.y + self.height / 2.0 - self.font_height;
-
-        let button_2_x = (self.game_square / 2.0) - self.font_width;
-        self.heart.update(button_2_x, button_y, cell_width);
+        self.title_height = cell_width * 0.75;
+        self.line_height = cell_width * 0.45;
 
-        let button_1_x = button_2_x - (cell_width * 1.25);
-        self.star_1.update(button_1_x, button_y, cell_width);
+        // three lines of results, then a button on each row
+        let buttons_y = self.title_height + (self.line_height * 3.0) + (cell_width * 0.15);
+        self.height = buttons_y + (cell_width * 0.75 * 3.0);
+        self.y = self.game_square / 2.0 - (self.height / 2.0);
 
-        let button_3_x = button_2_x + (cell_width * 1.25);
-        self.star_2.update(button_3_x, button_y, cell_width);
+        let buttons = [
+            &mut self.same_difficulty,
+            &mut self.choose,
+            &mut self.replay,
+        ];
+        for (i, button) in buttons.into_iter().enumerate() {
+            button.update(
+                self.x + (cell_width * 0.25),
+                self.y + buttons_y + (i as f32 * cell_width * 0.75),
+                self.width - (cell_width * 0.5),
+                cell_width * 0.5,
+            );
+        }
     }
 
     pub fn click_outside(&mut self, x: f32, y: f32) -> bool {